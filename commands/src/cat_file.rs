@@ -3,69 +3,164 @@
 use std::{
     fs::File,
     io::{BufRead, BufReader, Read},
+    path::Path,
     str::FromStr,
 };
 
 use utils::errors::{IoError, ObjectError, RebarError};
-use utils::types::ObjectType;
-
-use utils::globals::FILE_SIZE_LIMIT;
+use utils::types::{FileMode, ObjectType};
+
+/// Header-field placeholder for object types that carry no [`FileMode`],
+/// matching `commands::hash_object`'s `NO_MODE` constant.
+const NO_MODE: &str = "-";
+
+/// Upper bound passed to the zstd decoder's window-log-max setting, so an
+/// object written with any `compression_window_log` a repository has ever
+/// configured can still be decompressed here, regardless of what this
+/// repository's config says today.
+const MAX_DECODE_WINDOW_LOG: u32 = 31;
+
+/// Decompress a zstd-compressed object body, accepting any window size the
+/// object may have been written with.
+fn decompress(content: &[u8]) -> Result<Vec<u8>, ObjectError> {
+    let decompression_error = |e: std::io::Error| ObjectError::CorruptedContent {
+        reason: format!("Decompression failed: {e}"),
+    };
+
+    let mut decoder = zstd::stream::Decoder::new(content).map_err(decompression_error)?;
+    decoder
+        .window_log_max(MAX_DECODE_WINDOW_LOG)
+        .map_err(decompression_error)?;
+
+    let mut decompressed = Vec::new();
+    decoder
+        .read_to_end(&mut decompressed)
+        .map_err(decompression_error)?;
+    Ok(decompressed)
+}
 
-fn parse_header(header_line: &str) -> Result<(ObjectType, usize), RebarError> {
+fn parse_header(header_line: &str) -> Result<(ObjectType, Option<FileMode>, usize), RebarError> {
     let mut parts = header_line.split_whitespace();
 
     let object_type_str = parts.next().ok_or_else(|| ObjectError::MalformedHeader {
         reason: "Missing object type".to_string(),
     })?;
 
+    let mode_str = parts.next().ok_or_else(|| ObjectError::MalformedHeader {
+        reason: "Missing mode".to_string(),
+    })?;
+
     // Compressed size of the object
     let size_str = parts.next().ok_or_else(|| ObjectError::MalformedHeader {
         reason: "Missing size".to_string(),
     })?;
 
     let object_type = ObjectType::from_str(object_type_str)?;
+    let mode = if mode_str == NO_MODE {
+        None
+    } else {
+        Some(FileMode::parse(mode_str)?)
+    };
     let size = size_str
         .parse::<usize>()
         .map_err(|_| ObjectError::MalformedHeader {
             reason: format!("Invalid size: {size_str}"),
         })?;
 
-    Ok((object_type, size))
+    Ok((object_type, mode, size))
 }
 
 pub fn cat_file(hash: &str) -> Result<(), RebarError> {
     cat_file_from_path(hash, ".")
 }
 
-fn cat_file_from_path(hash: &str, start_path: &str) -> Result<(), RebarError> {
-    // find the repository and file
+pub(crate) fn cat_file_from_path(hash: &str, start_path: &str) -> Result<(), RebarError> {
     let repo_path = utils::find_repository(start_path).map_err(RebarError::from)?;
-    let path = format!("{repo_path}/objects/{hash}");
+    let (object_type, mode, decompressed) = read_decompressed_object(&repo_path, hash, start_path)?;
+
+    match object_type {
+        ObjectType::Blob => {
+            if let Some(mode) = mode {
+                eprintln!("mode: {mode}");
+            }
+            print!("{}", String::from_utf8_lossy(&decompressed));
+        }
+        ObjectType::Tree => {
+            let tree = utils::types::Tree::parse(ObjectError::decode_utf8(&decompressed)?)?;
+            for entry in &tree.entries {
+                println!("{} {} {}", entry.mode, entry.id, entry.name);
+            }
+        }
+        ObjectType::Commit => {
+            let commit = utils::types::Commit::parse(ObjectError::decode_utf8(&decompressed)?)?;
+            print!("{}", commit.serialize());
+        }
+        ObjectType::Manifest => {
+            if let Some(mode) = mode {
+                eprintln!("mode: {mode}");
+            }
+            let manifest = utils::types::Manifest::parse(ObjectError::decode_utf8(&decompressed)?)?;
+            let bundle = utils::bundle::Bundle::open(&repo_path)?;
+            for (index, chunk_id) in manifest.chunks.iter().enumerate() {
+                let compressed = bundle.read_chunk(chunk_id)?;
+                let chunk_content = decompress(&compressed)?;
+                utils::chunker::verify_chunk(index, chunk_id, &chunk_content)?;
+                print!("{}", String::from_utf8_lossy(&chunk_content));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Open, header-validate and decompress the object stored under `hash` in
+/// `repo_path`. The chunks a manifest references are not read through here:
+/// they come from the packed chunk store via `Bundle::read_chunk`.
+fn read_decompressed_object(
+    repo_path: &Path,
+    hash: &str,
+    start_path: &str,
+) -> Result<(ObjectType, Option<FileMode>, Vec<u8>), RebarError> {
+    let config = utils::config::Config::load_repository(start_path)?;
+    let path = repo_path.join("objects").join(hash);
+    let path_str = path.to_string_lossy().into_owned();
     let file = File::open(&path).map_err(|e| match e.kind() {
-        std::io::ErrorKind::NotFound => RebarError::Io(IoError::NotFound { path: path.clone() }),
+        std::io::ErrorKind::NotFound => {
+            RebarError::Io(IoError::NotFound { path: path_str.clone() })
+        }
         std::io::ErrorKind::PermissionDenied => RebarError::Io(IoError::Permission {
-            path: path.clone(),
+            path: path_str.clone(),
             source: e,
         }),
-        _ => RebarError::Io(IoError::Other(e)),
+        _ => RebarError::Io(IoError::open_file(path_str.clone(), e)),
     })?;
 
     let mut reader = BufReader::new(file);
     let mut header_line = String::new();
     reader.read_line(&mut header_line)?;
 
-    let (object_type, size) = parse_header(&header_line)?;
+    let (object_type, mode, size) = parse_header(&header_line)?;
 
-    if size > FILE_SIZE_LIMIT {
+    let file_size_limit = config.file_size_limit();
+    if size > file_size_limit {
         return Err(ObjectError::InvalidLength {
-            expected: FILE_SIZE_LIMIT,
+            expected: file_size_limit,
             actual: Some(size),
         }
         .into());
     }
 
+    // A single `read()` isn't guaranteed to fill the buffer (e.g. over a
+    // pipe), so keep reading until it's full or the source is exhausted.
     let mut content = vec![0; size];
-    let bytes_read = reader.read(&mut content)?;
+    let mut bytes_read = 0;
+    while bytes_read < size {
+        let n = reader.read(&mut content[bytes_read..])?;
+        if n == 0 {
+            break;
+        }
+        bytes_read += n;
+    }
 
     // Check if we read the expected amount
     if bytes_read != size {
@@ -86,18 +181,9 @@ fn cat_file_from_path(hash: &str, start_path: &str) -> Result<(), RebarError> {
         .into());
     }
 
-    match object_type {
-        ObjectType::Blob => {
-            let decompressed =
-                zstd::decode_all(&content[..]).map_err(|e| ObjectError::CorruptedContent {
-                    reason: format!("Decompression failed: {e}"),
-                })?;
-
-            print!("{}", String::from_utf8_lossy(&decompressed));
-        }
-    }
+    let decompressed = decompress(&content)?;
 
-    Ok(())
+    Ok((object_type, mode, decompressed))
 }
 
 #[cfg(test)]
@@ -107,6 +193,7 @@ mod tests {
     use std::io::Write;
     use tempfile::TempDir;
     use utils::errors::{ObjectError, RebarError};
+    use utils::globals::FILE_SIZE_LIMIT;
     use utils::types::ObjectType;
 
     // Helper function to create a test repository
@@ -126,7 +213,27 @@ mod tests {
         content: &str,
     ) -> Result<(), Box<dyn std::error::Error>> {
         let compressed_content = zstd::encode_all(content.as_bytes(), 3)?;
-        let header = format!("blob {}\n", compressed_content.len());
+        let header = format!("blob {NO_MODE} {}\n", compressed_content.len());
+
+        let objects_dir = temp_dir.path().join(".rebar").join("objects");
+        let object_path = objects_dir.join(hash);
+
+        let mut file = fs::File::create(object_path)?;
+        file.write_all(header.as_bytes())?;
+        file.write_all(&compressed_content)?;
+
+        Ok(())
+    }
+
+    // Helper function to create a tree object file from raw (possibly
+    // non-UTF-8) bytes
+    fn create_tree_object(
+        temp_dir: &TempDir,
+        hash: &str,
+        content: &[u8],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let compressed_content = zstd::encode_all(content, 3)?;
+        let header = format!("tree {NO_MODE} {}\n", compressed_content.len());
 
         let objects_dir = temp_dir.path().join(".rebar").join("objects");
         let object_path = objects_dir.join(hash);
@@ -140,10 +247,11 @@ mod tests {
 
     #[test]
     fn test_parse_header_valid() {
-        let header = "blob 1024\n";
+        let header = "blob - 1024\n";
         let result = parse_header(header).unwrap();
         assert_eq!(result.0, ObjectType::Blob);
-        assert_eq!(result.1, 1024);
+        assert_eq!(result.1, None);
+        assert_eq!(result.2, 1024);
     }
 
     #[test]
@@ -158,8 +266,19 @@ mod tests {
     }
 
     #[test]
-    fn test_parse_header_missing_size() {
+    fn test_parse_header_missing_mode() {
         let header = "blob\n";
+        match parse_header(header) {
+            Err(RebarError::Object(ObjectError::MalformedHeader { reason })) => {
+                assert_eq!(reason, "Missing mode");
+            }
+            _ => panic!("Expected MalformedHeader error for missing mode"),
+        }
+    }
+
+    #[test]
+    fn test_parse_header_missing_size() {
+        let header = "blob -\n";
         match parse_header(header) {
             Err(RebarError::Object(ObjectError::MalformedHeader { reason })) => {
                 assert_eq!(reason, "Missing size");
@@ -170,7 +289,7 @@ mod tests {
 
     #[test]
     fn test_parse_header_invalid_type() {
-        let header = "invalid 1024\n";
+        let header = "invalid - 1024\n";
         match parse_header(header) {
             Err(RebarError::Object(ObjectError::InvalidType { found })) => {
                 assert_eq!(found, "invalid");
@@ -181,7 +300,7 @@ mod tests {
 
     #[test]
     fn test_parse_header_invalid_size() {
-        let header = "blob notanumber\n";
+        let header = "blob - notanumber\n";
         match parse_header(header) {
             Err(RebarError::Object(ObjectError::MalformedHeader { reason })) => {
                 assert!(reason.contains("Invalid size: notanumber"));
@@ -192,7 +311,7 @@ mod tests {
 
     #[test]
     fn test_parse_header_negative_size() {
-        let header = "blob -100\n";
+        let header = "blob - -100\n";
         match parse_header(header) {
             Err(RebarError::Object(ObjectError::MalformedHeader { reason })) => {
                 assert!(reason.contains("Invalid size: -100"));
@@ -203,10 +322,19 @@ mod tests {
 
     #[test]
     fn test_parse_header_extra_whitespace() {
-        let header = "  blob   1024  \n";
+        let header = "  blob   -   1024  \n";
         let result = parse_header(header).unwrap();
         assert_eq!(result.0, ObjectType::Blob);
-        assert_eq!(result.1, 1024);
+        assert_eq!(result.2, 1024);
+    }
+
+    #[test]
+    fn test_parse_header_with_mode() {
+        let header = "blob file+x 1024\n";
+        let result = parse_header(header).unwrap();
+        assert_eq!(result.0, ObjectType::Blob);
+        assert_eq!(result.1, Some(FileMode::Regular { executable: true }));
+        assert_eq!(result.2, 1024);
     }
 
     #[test]
@@ -265,7 +393,7 @@ mod tests {
         let objects_dir = temp_dir.path().join(".rebar").join("objects");
         let object_path = objects_dir.join(&hash);
         let mut file = fs::File::create(object_path)?;
-        file.write_all(b"invalid 100\n")?;
+        file.write_all(b"invalid - 100\n")?;
         file.write_all(b"some content")?;
 
         let result = cat_file_from_path(&hash, temp_dir.path().to_str().unwrap());
@@ -288,7 +416,7 @@ mod tests {
         let objects_dir = temp_dir.path().join(".rebar").join("objects");
         let object_path = objects_dir.join(&hash);
         let mut file = fs::File::create(object_path)?;
-        file.write_all(b"blob\n")?;
+        file.write_all(b"blob -\n")?;
         file.write_all(b"some content")?;
 
         let result = cat_file_from_path(&hash, temp_dir.path().to_str().unwrap());
@@ -311,7 +439,7 @@ mod tests {
         let objects_dir = temp_dir.path().join(".rebar").join("objects");
         let object_path = objects_dir.join(&hash);
         let mut file = fs::File::create(object_path)?;
-        let header = format!("blob {}\n", FILE_SIZE_LIMIT + 1);
+        let header = format!("blob - {}\n", FILE_SIZE_LIMIT + 1);
         file.write_all(header.as_bytes())?;
         file.write_all(b"some content")?;
 
@@ -336,7 +464,7 @@ mod tests {
         let objects_dir = temp_dir.path().join(".rebar").join("objects");
         let object_path = objects_dir.join(&hash);
         let mut file = fs::File::create(object_path)?;
-        file.write_all(b"blob 100\n")?;
+        file.write_all(b"blob - 100\n")?;
         file.write_all(b"short")?; // Only 5 bytes, but header says 100
 
         let result = cat_file_from_path(&hash, temp_dir.path().to_str().unwrap());
@@ -360,7 +488,7 @@ mod tests {
         let objects_dir = temp_dir.path().join(".rebar").join("objects");
         let object_path = objects_dir.join(&hash);
         let mut file = fs::File::create(object_path)?;
-        file.write_all(b"blob 5\n")?;
+        file.write_all(b"blob - 5\n")?;
         file.write_all(b"this is much longer than 5 bytes")?;
 
         let result = cat_file_from_path(&hash, temp_dir.path().to_str().unwrap());
@@ -384,7 +512,7 @@ mod tests {
         let objects_dir = temp_dir.path().join(".rebar").join("objects");
         let object_path = objects_dir.join(&hash);
         let mut file = fs::File::create(object_path)?;
-        file.write_all(b"blob 10\n")?;
+        file.write_all(b"blob - 10\n")?;
         file.write_all(b"corrupted!")?; // Not valid zstd data
 
         let result = cat_file_from_path(&hash, temp_dir.path().to_str().unwrap());
@@ -442,19 +570,40 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_cat_file_tree_invalid_utf8() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = create_test_repository()?;
+        let hash = "l".repeat(64);
+
+        // 0xFF is never valid UTF-8, so this tree's content can't decode.
+        let content = b"100644 \xffbad.txt aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\n";
+        create_tree_object(&temp_dir, &hash, content)?;
+
+        let result = cat_file_from_path(&hash, temp_dir.path().to_str().unwrap());
+
+        match result {
+            Err(RebarError::Object(ObjectError::Utf8InvalidEncoding { offset, length })) => {
+                assert_eq!(offset, 7);
+                assert_eq!(length, 1);
+            }
+            _ => panic!("Expected Utf8InvalidEncoding error, got: {result:?}"),
+        }
+        Ok(())
+    }
+
     #[test]
     fn test_parse_header_zero_size() {
-        let header = "blob 0\n";
+        let header = "blob - 0\n";
         let result = parse_header(header).unwrap();
         assert_eq!(result.0, ObjectType::Blob);
-        assert_eq!(result.1, 0);
+        assert_eq!(result.2, 0);
     }
 
     #[test]
     fn test_parse_header_max_size() {
-        let header = format!("blob {}\n", usize::MAX);
+        let header = format!("blob - {}\n", usize::MAX);
         let result = parse_header(&header).unwrap();
         assert_eq!(result.0, ObjectType::Blob);
-        assert_eq!(result.1, usize::MAX);
+        assert_eq!(result.2, usize::MAX);
     }
 }
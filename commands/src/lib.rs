@@ -3,7 +3,11 @@
 pub mod cat_file;
 pub mod hash_object;
 pub mod init;
+pub mod pack;
+pub mod unpack;
 
 pub use cat_file::cat_file;
 pub use hash_object::hash_object;
 pub use init::init;
+pub use pack::pack;
+pub use unpack::unpack;
@@ -0,0 +1,26 @@
+//! Bundle `.rebar/objects` into a portable tar archive
+
+use std::fs::File;
+
+use utils::errors::{IoResultExt, RebarError};
+
+pub fn pack(output_path: &str) -> Result<(), RebarError> {
+    let repo_path = utils::find_repository(".").map_err(RebarError::from)?;
+    let objects_path = repo_path.join("objects");
+
+    let file = File::create(output_path)
+        .with_path(output_path)
+        .map_err(RebarError::from)?;
+
+    let mut archive = tar::Builder::new(file);
+    archive
+        .append_dir_all("objects", &objects_path)
+        .with_path(output_path)
+        .map_err(RebarError::from)?;
+    archive
+        .finish()
+        .with_path(output_path)
+        .map_err(RebarError::from)?;
+
+    Ok(())
+}
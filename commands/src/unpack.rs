@@ -0,0 +1,173 @@
+//! Restore a `.rebar/objects` tar archive produced by `pack`.
+//!
+//! Archives are untrusted input - they may come from a backup of unknown
+//! provenance or a peer repository - so every entry is checked before it
+//! touches disk: its path may only contain `Normal`/`CurDir` components (no
+//! `..` or absolute root, so nothing can escape `objects/`), its declared
+//! size must fit under `FILE_SIZE_LIMIT`, the archive as a whole is capped
+//! on total size and entry count, and the extracted object's name must still
+//! pass `validate_hex` (apart from the packed chunk store's fixed-name
+//! `bundle` and `bundle.idx` files).
+
+use std::fs::File;
+use std::path::{Component, Path, PathBuf};
+
+use tar::Archive;
+
+use utils::errors::{IoResultExt, ObjectError, RebarError};
+use utils::globals::FILE_SIZE_LIMIT;
+
+/// Maximum number of entries accepted from a single archive.
+const MAX_ENTRIES: usize = 1_000_000;
+
+/// Maximum combined uncompressed size of all entries in a single archive.
+const MAX_TOTAL_SIZE: u64 = FILE_SIZE_LIMIT as u64 * 4096;
+
+pub fn unpack(archive_path: &str) -> Result<(), RebarError> {
+    let repo_path = utils::find_repository(".").map_err(RebarError::from)?;
+    let objects_path = repo_path.join("objects");
+
+    let file = File::open(archive_path)
+        .with_path(archive_path)
+        .map_err(RebarError::from)?;
+    let mut archive = Archive::new(file);
+
+    let mut entry_count = 0usize;
+    let mut total_size: u64 = 0;
+
+    for entry in archive
+        .entries()
+        .with_path(archive_path)
+        .map_err(RebarError::from)?
+    {
+        let mut entry = entry.with_path(archive_path).map_err(RebarError::from)?;
+
+        entry_count += 1;
+        if entry_count > MAX_ENTRIES {
+            return Err(ObjectError::UnsafeArchiveEntry {
+                reason: format!("archive has more than {MAX_ENTRIES} entries"),
+            }
+            .into());
+        }
+
+        let entry_path = entry
+            .path()
+            .with_path(archive_path)
+            .map_err(RebarError::from)?
+            .into_owned();
+        let relative_path = sanitize_entry_path(&entry_path)?;
+
+        // `tar::Builder::append_dir_all` (used by `pack`) always emits a
+        // directory entry for `objects/` itself ahead of its children.
+        // Directory entries carry no object to validate - just make sure the
+        // directory exists and move on.
+        if entry.header().entry_type().is_dir() {
+            let destination = objects_path.join(&relative_path);
+            std::fs::create_dir_all(&destination)
+                .with_path(destination.to_string_lossy())
+                .map_err(RebarError::from)?;
+            continue;
+        }
+
+        let size = entry
+            .header()
+            .size()
+            .with_path(archive_path)
+            .map_err(RebarError::from)?;
+        if size > FILE_SIZE_LIMIT as u64 {
+            return Err(ObjectError::InvalidLength {
+                expected: FILE_SIZE_LIMIT,
+                actual: Some(size as usize),
+            }
+            .into());
+        }
+
+        total_size = total_size
+            .checked_add(size)
+            .ok_or_else(|| ObjectError::UnsafeArchiveEntry {
+                reason: "total unpacked size overflowed".to_string(),
+            })?;
+        if total_size > MAX_TOTAL_SIZE {
+            return Err(ObjectError::UnsafeArchiveEntry {
+                reason: format!(
+                    "archive exceeds total unpacked size limit of {MAX_TOTAL_SIZE} bytes"
+                ),
+            }
+            .into());
+        }
+
+        let name = relative_path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| ObjectError::UnsafeArchiveEntry {
+                reason: format!("archive entry has no valid object name: {entry_path:?}"),
+            })?;
+        // The packed chunk store (`utils::bundle`) keeps two fixed-name files
+        // beside the loose objects; everything else is named by its id.
+        if name != "bundle" && name != "bundle.idx" {
+            utils::validate_hex(name).map_err(RebarError::from)?;
+        }
+
+        let destination = objects_path.join(&relative_path);
+        let mut out = File::create(&destination)
+            .with_path(destination.to_string_lossy())
+            .map_err(RebarError::from)?;
+        std::io::copy(&mut entry, &mut out)
+            .with_path(destination.to_string_lossy())
+            .map_err(RebarError::from)?;
+    }
+
+    Ok(())
+}
+
+/// Reject any `..` or absolute-root component, then strip the leading
+/// `objects/` prefix `pack` writes entries under, leaving just `<hex>`.
+fn sanitize_entry_path(path: &Path) -> Result<PathBuf, RebarError> {
+    let mut sanitized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => sanitized.push(part),
+            Component::CurDir => {}
+            _ => {
+                return Err(ObjectError::UnsafeArchiveEntry {
+                    reason: format!("archive entry has an unsafe path component: {path:?}"),
+                }
+                .into());
+            }
+        }
+    }
+
+    Ok(sanitized
+        .strip_prefix("objects")
+        .unwrap_or(&sanitized)
+        .to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_entry_path_strips_objects_prefix() {
+        let result = sanitize_entry_path(Path::new("objects/abcd1234")).unwrap();
+        assert_eq!(result, Path::new("abcd1234"));
+    }
+
+    #[test]
+    fn test_sanitize_entry_path_rejects_parent_dir() {
+        let result = sanitize_entry_path(Path::new("objects/../../etc/passwd"));
+        assert!(matches!(
+            result,
+            Err(RebarError::Object(ObjectError::UnsafeArchiveEntry { .. }))
+        ));
+    }
+
+    #[test]
+    fn test_sanitize_entry_path_rejects_absolute_root() {
+        let result = sanitize_entry_path(Path::new("/etc/passwd"));
+        assert!(matches!(
+            result,
+            Err(RebarError::Object(ObjectError::UnsafeArchiveEntry { .. }))
+        ));
+    }
+}
@@ -1,9 +1,6 @@
 //! Rebar - A new version control system written in Rust
 
-mod utils;
-mod commands;
-
-use crate::utils::errors::{InputError, RebarError};
+use utils::errors::{InputError, RebarError};
 
 use clap::{Parser, Subcommand};
 
@@ -31,6 +28,19 @@ enum Command {
         /// Should the object be written to the current repository
         #[arg(short, long)]
         write: bool,
+        /// Overwrite an existing object with the same id
+        #[arg(long)]
+        force: bool,
+    },
+    /// Bundle .rebar/objects into a portable tar archive
+    Pack {
+        /// Path to write the archive to
+        output_path: String,
+    },
+    /// Restore a .rebar/objects tar archive produced by `pack`
+    Unpack {
+        /// Path to the archive to restore
+        archive_path: String,
     },
 }
 
@@ -44,17 +54,22 @@ fn main() {
 
     // TODO: Implement other commands
     let result = match args.command {
-        Command::Init => crate::commands::init().map_err(RebarError::from),
+        Command::Init => commands::init().map_err(RebarError::from),
         Command::CatFile { hash } => {
-            if let Err(e) = crate::utils::validate_hex(&hash) {
+            if let Err(e) = utils::validate_hex(&hash) {
                 handle_error(RebarError::Input(InputError::InvalidArgument {
                     argument: "hash".to_string(),
                     reason: e.to_string(),
                 }));
             }
-            crate::commands::cat_file(&hash)
+            commands::cat_file(&hash)
         }
-        Command::HashObject { path, stdin, write } => {
+        Command::HashObject {
+            path,
+            stdin,
+            write,
+            force,
+        } => {
             if stdin && path.is_some() {
                 handle_error(RebarError::Input(
                     InputError::ArgumentConflict {
@@ -68,12 +83,15 @@ fn main() {
             }
 
             if let Some(ref p) = path
-                && let Err(e) = crate::utils::validate_path(p)
+                && let Err(e) = utils::validate_path(p)
             {
                 handle_error(RebarError::from(e))
             }
-            crate::commands::hash_object(path.as_deref(), stdin, write)
+            let path = path.as_deref().map(std::path::Path::new);
+            commands::hash_object(path, stdin, write, force)
         }
+        Command::Pack { output_path } => commands::pack(&output_path),
+        Command::Unpack { archive_path } => commands::unpack(&archive_path),
     };
 
     if let Err(e) = result {
@@ -11,6 +11,12 @@ struct Config {
 
     #[serde(default)]
     compression_level: CompressionLevel,
+
+    #[serde(default)]
+    compression_window_log: CompressionWindowLog,
+
+    #[serde(default)]
+    enable_long_distance_matching: EnableLongDistanceMatching,
 }
 
 #[derive(Deserialize, Debug)]
@@ -29,6 +35,31 @@ impl Default for CompressionLevel {
     }
 }
 
+/// zstd window log (2^log bytes of history the compressor can reference).
+/// Bigger windows find matches further back in highly-redundant files, at
+/// the cost of that much more memory held by the encoder/decoder - `0` means
+/// "unset", leaving zstd's level-derived default in place. This, and
+/// `enable_long_distance_matching` below, are also overridable at runtime
+/// per-repository via `.rebar/config` - see `Config::compression_window_log`.
+#[derive(Deserialize, Debug)]
+struct CompressionWindowLog(u8);
+impl Default for CompressionWindowLog {
+    fn default() -> Self {
+        CompressionWindowLog(0)
+    }
+}
+
+/// Whether to enable zstd's long-distance matching mode, which is what makes
+/// a large `compression_window_log` actually useful for finding distant
+/// repeats cheaply.
+#[derive(Deserialize, Debug)]
+struct EnableLongDistanceMatching(bool);
+impl Default for EnableLongDistanceMatching {
+    fn default() -> Self {
+        EnableLongDistanceMatching(false)
+    }
+}
+
 fn main() {
     // Re-run this script if `config.json` changes.
     println!("cargo:rerun-if-changed=config.json");
@@ -57,4 +88,14 @@ fn main() {
         "cargo:rustc-env=COMPRESSION_LEVEL={}",
         config.compression_level.0
     );
+
+    println!(
+        "cargo:rustc-env=COMPRESSION_WINDOW_LOG={}",
+        config.compression_window_log.0
+    );
+
+    println!(
+        "cargo:rustc-env=ENABLE_LONG_DISTANCE_MATCHING={}",
+        config.enable_long_distance_matching.0
+    );
 }
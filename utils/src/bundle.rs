@@ -0,0 +1,235 @@
+//! A packed store for content-defined chunks (see [`crate::chunker`]).
+//!
+//! Each chunk a [`crate::types::Manifest`] references used to be written as
+//! its own loose object file under `objects/<hash>`, which wastes space and
+//! inodes once a large file is split into thousands of small chunks. Instead,
+//! chunk bytes are appended once each to a single `objects/bundle` file, with
+//! their id, offset and length recorded as a line in a companion
+//! `objects/bundle.idx` - so a highly-redundant large file costs one copy of
+//! its unique chunks plus a handful of index lines, not one small file per
+//! chunk.
+
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use crate::errors::{IoError, ObjectError, RebarError};
+use crate::types::{HashKind, ObjectId};
+
+/// A handle onto a repository's packed chunk store.
+#[derive(Debug)]
+pub struct Bundle {
+    bundle_path: PathBuf,
+    index_path: PathBuf,
+    index: HashMap<ObjectId, (u64, u64)>,
+}
+
+impl Bundle {
+    /// Open the packed chunk store under `repo_path` (a repository's
+    /// `.rebar` directory), loading its index into memory. Neither the
+    /// bundle nor its index need to exist yet - an empty store behaves as if
+    /// they're both empty.
+    pub fn open(repo_path: &Path) -> Result<Self, RebarError> {
+        let bundle_path = repo_path.join("objects").join("bundle");
+        let index_path = repo_path.join("objects").join("bundle.idx");
+
+        let mut index = HashMap::new();
+        if index_path.exists() {
+            let index_path_str = index_path.to_string_lossy().into_owned();
+            let content = std::fs::read_to_string(&index_path)
+                .map_err(|e| IoError::read_file(index_path_str, e))?;
+
+            for (line_no, line) in content.lines().enumerate() {
+                let (id, offset, length) = parse_index_line(line).ok_or_else(|| {
+                    ObjectError::MalformedHeader {
+                        reason: format!("bundle index line {line_no}: '{line}'"),
+                    }
+                })?;
+                index.insert(id, (offset, length));
+            }
+        }
+
+        Ok(Self {
+            bundle_path,
+            index_path,
+            index,
+        })
+    }
+
+    /// Whether `id` is already stored in this bundle.
+    pub fn contains(&self, id: &ObjectId) -> bool {
+        self.index.contains_key(id)
+    }
+
+    /// Append `content` to the bundle under `id`, unless it's already
+    /// present - chunks are expected to recur across and within files, and
+    /// this is the chunk store's only dedup point.
+    pub fn write_chunk(&mut self, id: &ObjectId, content: &[u8]) -> Result<(), RebarError> {
+        if self.contains(id) {
+            return Ok(());
+        }
+
+        let bundle_path_str = self.bundle_path.to_string_lossy().into_owned();
+        let mut bundle_file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.bundle_path)
+            .map_err(|e| IoError::create_file(bundle_path_str.clone(), e))?;
+        let offset = bundle_file
+            .metadata()
+            .map_err(|e| IoError::read_file(bundle_path_str.clone(), e))?
+            .len();
+        bundle_file
+            .write_all(content)
+            .map_err(|e| IoError::write_file(bundle_path_str, e))?;
+
+        let index_path_str = self.index_path.to_string_lossy().into_owned();
+        let mut index_file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.index_path)
+            .map_err(|e| IoError::create_file(index_path_str.clone(), e))?;
+        writeln!(index_file, "{} {} {}", id.to_hex(), offset, content.len())
+            .map_err(|e| IoError::write_file(index_path_str, e))?;
+
+        self.index.insert(id.clone(), (offset, content.len() as u64));
+        Ok(())
+    }
+
+    /// Read the bytes stored under `id` back out of the bundle.
+    pub fn read_chunk(&self, id: &ObjectId) -> Result<Vec<u8>, RebarError> {
+        let (offset, length) = *self
+            .index
+            .get(id)
+            .ok_or_else(|| ObjectError::MissingChunk { id: id.clone() })?;
+
+        let bundle_path_str = self.bundle_path.to_string_lossy().into_owned();
+        let mut file = File::open(&self.bundle_path)
+            .map_err(|e| IoError::open_file(bundle_path_str.clone(), e))?;
+        file.seek(SeekFrom::Start(offset))
+            .map_err(|e| IoError::read_file(bundle_path_str.clone(), e))?;
+
+        let mut content = vec![0u8; length as usize];
+        file.read_exact(&mut content)
+            .map_err(|e| IoError::read_file(bundle_path_str, e))?;
+
+        Ok(content)
+    }
+}
+
+/// Parse one `objects/bundle.idx` line: `<hex id> <offset> <length>`.
+fn parse_index_line(line: &str) -> Option<(ObjectId, u64, u64)> {
+    let mut parts = line.split_whitespace();
+    let id = ObjectId::from_hex(parts.next()?, HashKind::Sha256).ok()?;
+    let offset = parts.next()?.parse().ok()?;
+    let length = parts.next()?.parse().ok()?;
+    Some((id, offset, length))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn repo() -> (TempDir, std::path::PathBuf) {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path().join(".rebar");
+        std::fs::create_dir_all(repo_path.join("objects")).unwrap();
+        (temp_dir, repo_path)
+    }
+
+    fn id_for(data: &[u8]) -> ObjectId {
+        crate::chunker::hash_chunk(data)
+    }
+
+    #[test]
+    fn test_open_empty_bundle() {
+        let (_temp_dir, repo_path) = repo();
+        let bundle = Bundle::open(&repo_path).unwrap();
+        assert!(!bundle.contains(&id_for(b"anything")));
+    }
+
+    #[test]
+    fn test_write_then_read_chunk() {
+        let (_temp_dir, repo_path) = repo();
+        let mut bundle = Bundle::open(&repo_path).unwrap();
+
+        let id = id_for(b"hello chunk");
+        bundle.write_chunk(&id, b"hello chunk").unwrap();
+
+        assert!(bundle.contains(&id));
+        assert_eq!(bundle.read_chunk(&id).unwrap(), b"hello chunk");
+    }
+
+    #[test]
+    fn test_write_dedups_identical_chunk() {
+        let (_temp_dir, repo_path) = repo();
+        let mut bundle = Bundle::open(&repo_path).unwrap();
+
+        let id = id_for(b"repeated");
+        bundle.write_chunk(&id, b"repeated").unwrap();
+        bundle.write_chunk(&id, b"repeated").unwrap();
+
+        let bundle_path = repo_path.join("objects").join("bundle");
+        assert_eq!(std::fs::read(bundle_path).unwrap(), b"repeated");
+    }
+
+    #[test]
+    fn test_multiple_chunks_read_back_independently() {
+        let (_temp_dir, repo_path) = repo();
+        let mut bundle = Bundle::open(&repo_path).unwrap();
+
+        let id_a = id_for(b"first chunk");
+        let id_b = id_for(b"second, longer chunk");
+        bundle.write_chunk(&id_a, b"first chunk").unwrap();
+        bundle.write_chunk(&id_b, b"second, longer chunk").unwrap();
+
+        assert_eq!(bundle.read_chunk(&id_a).unwrap(), b"first chunk");
+        assert_eq!(bundle.read_chunk(&id_b).unwrap(), b"second, longer chunk");
+    }
+
+    #[test]
+    fn test_read_missing_chunk() {
+        let (_temp_dir, repo_path) = repo();
+        let bundle = Bundle::open(&repo_path).unwrap();
+
+        let id = id_for(b"never written");
+        match bundle.read_chunk(&id) {
+            Err(RebarError::Object(ObjectError::MissingChunk { id: missing })) => {
+                assert_eq!(missing, id);
+            }
+            other => panic!("Expected MissingChunk, got: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_reopen_loads_existing_index() {
+        let (_temp_dir, repo_path) = repo();
+        let id = id_for(b"persisted chunk");
+
+        {
+            let mut bundle = Bundle::open(&repo_path).unwrap();
+            bundle.write_chunk(&id, b"persisted chunk").unwrap();
+        }
+
+        let reopened = Bundle::open(&repo_path).unwrap();
+        assert!(reopened.contains(&id));
+        assert_eq!(reopened.read_chunk(&id).unwrap(), b"persisted chunk");
+    }
+
+    #[test]
+    fn test_reopen_rejects_malformed_index() {
+        let (_temp_dir, repo_path) = repo();
+        std::fs::write(
+            repo_path.join("objects").join("bundle.idx"),
+            "not a valid index line\n",
+        )
+        .unwrap();
+
+        match Bundle::open(&repo_path) {
+            Err(RebarError::Object(ObjectError::MalformedHeader { .. })) => {}
+            other => panic!("Expected MalformedHeader, got: {other:?}"),
+        }
+    }
+}
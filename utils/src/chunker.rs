@@ -0,0 +1,182 @@
+//! Content-defined chunking (FastCDC-style) for blobs over `FILE_SIZE_LIMIT`.
+//!
+//! Splitting a large blob into content-defined chunks means an edit to one
+//! part of a huge file only rewrites the chunks whose content actually
+//! changed, and identical chunks across different files are deduplicated
+//! once they're addressed by hash rather than by file.
+
+use sha2::{Digest, Sha256};
+
+use crate::errors::ObjectError;
+use crate::types::{HashKind, ObjectId};
+
+/// Minimum chunk size; a boundary is never declared below this.
+pub const MIN_CHUNK_SIZE: usize = 2 * 1024;
+/// Target average chunk size once past `MIN_CHUNK_SIZE`.
+pub const AVG_CHUNK_SIZE: usize = 8 * 1024;
+/// Hard ceiling; a boundary is always forced at this size.
+pub const MAX_CHUNK_SIZE: usize = 64 * 1024;
+
+/// A gear/Rabin-style fingerprint table: one random `u64` per input byte
+/// value, generated deterministically so the table doesn't need to live as
+/// 256 literals in source.
+static GEAR: [u64; 256] = generate_gear_table();
+
+const fn generate_gear_table() -> [u64; 256] {
+    let mut table = [0u64; 256];
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut i = 0;
+    while i < 256 {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        table[i] = state;
+        i += 1;
+    }
+    table
+}
+
+/// A single content-defined chunk, addressed by the hash of its own bytes.
+pub struct Chunk {
+    pub id: ObjectId,
+    pub data: Vec<u8>,
+}
+
+/// Split `data` into content-defined chunks.
+///
+/// Each chunk is hashed independently (see [`hash_chunk`]), so the resulting
+/// list of ids is stable across files that happen to share content.
+pub fn chunk(data: &[u8]) -> Vec<Chunk> {
+    let mut chunks = Vec::new();
+    let mut start = 0;
+
+    while start < data.len() {
+        let end = start + find_boundary(&data[start..]);
+        let slice = &data[start..end];
+        chunks.push(Chunk {
+            id: hash_chunk(slice),
+            data: slice.to_vec(),
+        });
+        start = end;
+    }
+
+    chunks
+}
+
+/// Find the offset of the next chunk boundary within `data`, relative to its
+/// start. Slides a gear-hash fingerprint over the bytes and cuts as soon as
+/// the fingerprint's low bits are all zero, using a stricter (harder to
+/// match) mask before `AVG_CHUNK_SIZE` and a looser one after, so chunks
+/// cluster around the target size. Always cuts by `MAX_CHUNK_SIZE`.
+fn find_boundary(data: &[u8]) -> usize {
+    if data.len() <= MIN_CHUNK_SIZE {
+        return data.len();
+    }
+
+    let bits = AVG_CHUNK_SIZE.trailing_zeros();
+    let mask_stricter: u64 = (1u64 << (bits + 1)) - 1;
+    let mask_looser: u64 = (1u64 << (bits - 1)) - 1;
+
+    let max = data.len().min(MAX_CHUNK_SIZE);
+    let mut fingerprint: u64 = 0;
+
+    for (i, &byte) in data.iter().enumerate().take(max).skip(MIN_CHUNK_SIZE) {
+        fingerprint = (fingerprint << 1).wrapping_add(GEAR[byte as usize]);
+
+        let mask = if i < AVG_CHUNK_SIZE {
+            mask_stricter
+        } else {
+            mask_looser
+        };
+        if fingerprint & mask == 0 {
+            return i + 1;
+        }
+    }
+
+    max
+}
+
+/// Hash a single chunk's content into its [`ObjectId`].
+pub fn hash_chunk(data: &[u8]) -> ObjectId {
+    ObjectId::new(HashKind::Sha256, Sha256::digest(data).to_vec())
+}
+
+/// Recompute `data`'s hash and compare it against the id it was stored
+/// under, naming the offending block on mismatch.
+pub fn verify_chunk(index: usize, expected: &ObjectId, data: &[u8]) -> Result<(), ObjectError> {
+    let actual = hash_chunk(data);
+    if &actual != expected {
+        return Err(ObjectError::BlockHashMismatch {
+            index,
+            expected: expected.clone(),
+            actual,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunk_small_input_is_single_chunk() {
+        let data = vec![0u8; 128];
+        let chunks = chunk(&data);
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].data, data);
+    }
+
+    #[test]
+    fn test_chunk_respects_max_size() {
+        let data = vec![7u8; MAX_CHUNK_SIZE * 3];
+        let chunks = chunk(&data);
+        assert!(chunks.iter().all(|c| c.data.len() <= MAX_CHUNK_SIZE));
+        assert!(chunks.iter().all(|c| !c.data.is_empty()));
+    }
+
+    #[test]
+    fn test_chunk_reassembles_to_original() {
+        let data: Vec<u8> = (0..MAX_CHUNK_SIZE * 2)
+            .map(|i| (i % 251) as u8)
+            .collect();
+        let chunks = chunk(&data);
+        let reassembled: Vec<u8> = chunks.iter().flat_map(|c| c.data.clone()).collect();
+        assert_eq!(reassembled, data);
+    }
+
+    #[test]
+    fn test_chunk_ids_are_hashes_of_chunk_content() {
+        let repeated = vec![42u8; MIN_CHUNK_SIZE];
+        let mut data = repeated.clone();
+        data.extend_from_slice(&repeated);
+        let chunks = chunk(&data);
+        assert!(chunks.iter().all(|c| c.id == hash_chunk(&c.data)));
+    }
+
+    #[test]
+    fn test_verify_chunk_ok() {
+        let data = b"hello chunk";
+        let id = hash_chunk(data);
+        assert!(verify_chunk(0, &id, data).is_ok());
+    }
+
+    #[test]
+    fn test_verify_chunk_mismatch() {
+        let data = b"hello chunk";
+        let id = hash_chunk(data);
+        let result = verify_chunk(3, &id, b"tampered data");
+        match result {
+            Err(ObjectError::BlockHashMismatch {
+                index,
+                expected,
+                actual,
+            }) => {
+                assert_eq!(index, 3);
+                assert_eq!(expected, id);
+                assert_ne!(actual, id);
+            }
+            _ => panic!("Expected BlockHashMismatch error"),
+        }
+    }
+}
@@ -0,0 +1,408 @@
+//! Runtime `.rebar/config` subsystem.
+//!
+//! Parses an INI-style format modeled on Mercurial's `hgrc`: section headers
+//! match `^\[([^\[]+)\]`, `key = value` items match
+//! `^([^=\s][^=]*?)\s*=\s*((.*\S)?)`, indented lines continue the previous
+//! value, `;`/`#`/blank lines are comments, `%unset <key>` deletes a
+//! previously-set key, and `%include <path>` recursively merges another
+//! config file. Later files, and later lines within a file, override earlier
+//! values. This is what lets `file_size_limit` (baked in at build time via
+//! `build.rs`) be overridden per-repository without a recompile - see
+//! [`Config::file_size_limit`].
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::errors::{IoResultExt, ObjectError, RebarError};
+use crate::globals;
+
+/// Bound on recursive `%include` depth, so a misconfigured or malicious
+/// include chain can't blow the stack.
+const MAX_INCLUDE_DEPTH: usize = 16;
+
+/// A merged view of a repository's `.rebar/config` and any files it
+/// `%include`s.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Config {
+    sections: HashMap<String, HashMap<String, String>>,
+}
+
+impl Config {
+    pub fn get(&self, section: &str, key: &str) -> Option<&str> {
+        self.sections.get(section)?.get(key).map(String::as_str)
+    }
+
+    pub fn set(&mut self, section: &str, key: &str, value: String) {
+        self.sections
+            .entry(section.to_string())
+            .or_default()
+            .insert(key.to_string(), value);
+    }
+
+    pub fn unset(&mut self, section: &str, key: &str) {
+        if let Some(entries) = self.sections.get_mut(section) {
+            entries.remove(key);
+        }
+    }
+
+    /// `file_size_limit`, falling back to the build-time default when unset
+    /// or unparsable.
+    pub fn file_size_limit(&self) -> usize {
+        self.get("rebar", "file_size_limit")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(globals::FILE_SIZE_LIMIT)
+    }
+
+    /// zstd window log to compress objects with, falling back to the
+    /// build-time default when unset or unparsable. A bigger window lets the
+    /// compressor find matches further back in highly-redundant files, at
+    /// the cost of that much more memory held by the encoder and, later, by
+    /// `cat_file`'s decoder.
+    pub fn compression_window_log(&self) -> u32 {
+        self.get("rebar", "compression_window_log")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(globals::COMPRESSION_WINDOW_LOG)
+    }
+
+    /// Whether to enable zstd long-distance matching, falling back to the
+    /// build-time default when unset or unparsable. Only useful alongside a
+    /// `compression_window_log` large enough to span the distance between
+    /// repeats.
+    pub fn enable_long_distance_matching(&self) -> bool {
+        self.get("rebar", "enable_long_distance_matching")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(globals::ENABLE_LONG_DISTANCE_MATCHING)
+    }
+
+    /// Load the config for the repository containing `start_path`. Returns
+    /// an empty (all-default) config if the repository has no config file.
+    pub fn load_repository(start_path: &str) -> Result<Self, RebarError> {
+        let repo_path = crate::find_repository(start_path)?;
+        let config_path = repo_path.join("config");
+
+        let mut config = Self::default();
+        if config_path.exists() {
+            let mut seen = Vec::new();
+            config.merge_file(&config_path, &mut seen, 0)?;
+        }
+        Ok(config)
+    }
+
+    fn merge_file(
+        &mut self,
+        path: &Path,
+        seen: &mut Vec<PathBuf>,
+        depth: usize,
+    ) -> Result<(), RebarError> {
+        if depth > MAX_INCLUDE_DEPTH {
+            return Err(ObjectError::MalformedHeader {
+                reason: format!("%include depth exceeded at {}", path.display()),
+            }
+            .into());
+        }
+
+        let canonical = path
+            .canonicalize()
+            .with_path(path.to_string_lossy())
+            .map_err(RebarError::from)?;
+        if seen.contains(&canonical) {
+            return Err(ObjectError::MalformedHeader {
+                reason: format!("%include cycle detected at {}", path.display()),
+            }
+            .into());
+        }
+        seen.push(canonical);
+
+        let content = std::fs::read_to_string(path)
+            .with_path(path.to_string_lossy())
+            .map_err(RebarError::from)?;
+        self.merge_str(&content, path, seen, depth)?;
+
+        seen.pop();
+        Ok(())
+    }
+
+    fn merge_str(
+        &mut self,
+        content: &str,
+        base_path: &Path,
+        seen: &mut Vec<PathBuf>,
+        depth: usize,
+    ) -> Result<(), RebarError> {
+        let mut section = String::new();
+        let mut last_key: Option<(String, String)> = None;
+
+        for raw_line in content.lines() {
+            if raw_line.trim().is_empty() || is_comment(raw_line) {
+                continue;
+            }
+
+            // Leading whitespace continues the previous value.
+            if starts_with_whitespace(raw_line) {
+                if let Some((section, key)) = &last_key {
+                    if let Some(value) = self
+                        .sections
+                        .get_mut(section)
+                        .and_then(|entries| entries.get_mut(key))
+                    {
+                        value.push('\n');
+                        value.push_str(raw_line.trim());
+                    }
+                }
+                continue;
+            }
+
+            let line = raw_line.trim();
+
+            if let Some(key) = line.strip_prefix("%unset ") {
+                self.unset(&section, key.trim());
+                last_key = None;
+                continue;
+            }
+
+            if let Some(include_path) = line.strip_prefix("%include ") {
+                let resolved = resolve_include_path(base_path, include_path.trim());
+                self.merge_file(&resolved, seen, depth + 1)?;
+                last_key = None;
+                continue;
+            }
+
+            if let Some(name) = parse_section_header(line) {
+                section = name.to_string();
+                last_key = None;
+                continue;
+            }
+
+            if let Some((key, value)) = parse_key_value(line) {
+                self.set(&section, key, value.to_string());
+                last_key = Some((section.clone(), key.to_string()));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn is_comment(line: &str) -> bool {
+    matches!(line.trim_start().chars().next(), Some(';') | Some('#'))
+}
+
+fn starts_with_whitespace(line: &str) -> bool {
+    matches!(line.chars().next(), Some(c) if c.is_whitespace())
+}
+
+/// Matches `^\[([^\[]+)\]`.
+fn parse_section_header(line: &str) -> Option<&str> {
+    let inner = line.strip_prefix('[')?.strip_suffix(']')?;
+    if inner.is_empty() || inner.contains('[') {
+        return None;
+    }
+    Some(inner)
+}
+
+/// Matches `^([^=\s][^=]*?)\s*=\s*((.*\S)?)`.
+fn parse_key_value(line: &str) -> Option<(&str, &str)> {
+    let (key, value) = line.split_once('=')?;
+    let key = key.trim();
+    if key.is_empty() || key.starts_with(char::is_whitespace) {
+        return None;
+    }
+    Some((key, value.trim()))
+}
+
+fn resolve_include_path(base_path: &Path, include_path: &str) -> PathBuf {
+    let include_path = Path::new(include_path);
+    if include_path.is_absolute() {
+        include_path.to_path_buf()
+    } else {
+        base_path
+            .parent()
+            .unwrap_or_else(|| Path::new("."))
+            .join(include_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_section_header() {
+        assert_eq!(parse_section_header("[rebar]"), Some("rebar"));
+        assert_eq!(parse_section_header("[nested]]"), Some("nested]"));
+        assert_eq!(parse_section_header("not a section"), None);
+    }
+
+    #[test]
+    fn test_parse_key_value() {
+        assert_eq!(
+            parse_key_value("file_size_limit = 1048576"),
+            Some(("file_size_limit", "1048576"))
+        );
+        assert_eq!(parse_key_value("= no key"), None);
+        assert_eq!(parse_key_value("no value here"), None);
+    }
+
+    #[test]
+    fn test_merge_str_sets_values_by_section() {
+        let mut config = Config::default();
+        config
+            .merge_str(
+                "[rebar]\nfile_size_limit = 2048\ncompression_level = 5\n",
+                Path::new("config"),
+                &mut Vec::new(),
+                0,
+            )
+            .unwrap();
+
+        assert_eq!(config.get("rebar", "file_size_limit"), Some("2048"));
+        assert_eq!(config.get("rebar", "compression_level"), Some("5"));
+    }
+
+    #[test]
+    fn test_merge_str_ignores_comments_and_blanks() {
+        let mut config = Config::default();
+        config
+            .merge_str(
+                "; a comment\n\n# another comment\n[rebar]\nkey = value\n",
+                Path::new("config"),
+                &mut Vec::new(),
+                0,
+            )
+            .unwrap();
+
+        assert_eq!(config.get("rebar", "key"), Some("value"));
+    }
+
+    #[test]
+    fn test_merge_str_continuation_line() {
+        let mut config = Config::default();
+        config
+            .merge_str(
+                "[rebar]\nmessage = first line\n  second line\n",
+                Path::new("config"),
+                &mut Vec::new(),
+                0,
+            )
+            .unwrap();
+
+        assert_eq!(
+            config.get("rebar", "message"),
+            Some("first line\nsecond line")
+        );
+    }
+
+    #[test]
+    fn test_merge_str_unset_removes_key() {
+        let mut config = Config::default();
+        config
+            .merge_str(
+                "[rebar]\nkey = value\n%unset key\n",
+                Path::new("config"),
+                &mut Vec::new(),
+                0,
+            )
+            .unwrap();
+
+        assert_eq!(config.get("rebar", "key"), None);
+    }
+
+    #[test]
+    fn test_merge_str_later_values_override_earlier() {
+        let mut config = Config::default();
+        config
+            .merge_str(
+                "[rebar]\nkey = first\nkey = second\n",
+                Path::new("config"),
+                &mut Vec::new(),
+                0,
+            )
+            .unwrap();
+
+        assert_eq!(config.get("rebar", "key"), Some("second"));
+    }
+
+    #[test]
+    fn test_merge_file_includes_other_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let included = temp_dir.path().join("extra.conf");
+        fs::write(&included, "[rebar]\nfrom_include = yes\n").unwrap();
+
+        let main = temp_dir.path().join("config");
+        fs::write(
+            &main,
+            format!("[rebar]\nbase = yes\n%include {}\n", included.display()),
+        )
+        .unwrap();
+
+        let mut config = Config::default();
+        config.merge_file(&main, &mut Vec::new(), 0).unwrap();
+
+        assert_eq!(config.get("rebar", "base"), Some("yes"));
+        assert_eq!(config.get("rebar", "from_include"), Some("yes"));
+    }
+
+    #[test]
+    fn test_merge_file_detects_include_cycle() {
+        let temp_dir = TempDir::new().unwrap();
+        let a = temp_dir.path().join("a.conf");
+        let b = temp_dir.path().join("b.conf");
+        fs::write(&a, format!("%include {}\n", b.display())).unwrap();
+        fs::write(&b, format!("%include {}\n", a.display())).unwrap();
+
+        let mut config = Config::default();
+        let result = config.merge_file(&a, &mut Vec::new(), 0);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_file_size_limit_falls_back_to_default() {
+        let config = Config::default();
+        assert_eq!(config.file_size_limit(), globals::FILE_SIZE_LIMIT);
+    }
+
+    #[test]
+    fn test_file_size_limit_uses_configured_value() {
+        let mut config = Config::default();
+        config.set("rebar", "file_size_limit", "4096".to_string());
+        assert_eq!(config.file_size_limit(), 4096);
+    }
+
+    #[test]
+    fn test_compression_window_log_falls_back_to_default() {
+        let config = Config::default();
+        assert_eq!(
+            config.compression_window_log(),
+            globals::COMPRESSION_WINDOW_LOG
+        );
+    }
+
+    #[test]
+    fn test_compression_window_log_uses_configured_value() {
+        let mut config = Config::default();
+        config.set("rebar", "compression_window_log", "27".to_string());
+        assert_eq!(config.compression_window_log(), 27);
+    }
+
+    #[test]
+    fn test_enable_long_distance_matching_falls_back_to_default() {
+        let config = Config::default();
+        assert_eq!(
+            config.enable_long_distance_matching(),
+            globals::ENABLE_LONG_DISTANCE_MATCHING
+        );
+    }
+
+    #[test]
+    fn test_enable_long_distance_matching_uses_configured_value() {
+        let mut config = Config::default();
+        config.set(
+            "rebar",
+            "enable_long_distance_matching",
+            "true".to_string(),
+        );
+        assert!(config.enable_long_distance_matching());
+    }
+}
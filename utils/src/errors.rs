@@ -1,5 +1,6 @@
 //! Error handling for the Rebar VCS
 
+use std::ffi::OsString;
 use std::fmt;
 
 use crate::globals::HASH_SIZE;
@@ -34,6 +35,117 @@ impl std::error::Error for RebarError {
     }
 }
 
+impl RebarError {
+    /// A stable, machine-readable identifier (e.g. `rebar.io.already_exists`)
+    /// for this error, following the sub-error's own [`IoError::code`] /
+    /// [`HashError::code`] / [`ObjectError::code`] / [`InputError::code`].
+    /// Scripts and CI should match on this rather than parsing [`Display`]
+    /// output, which is free to change wording.
+    pub fn code(&self) -> &'static str {
+        match self {
+            RebarError::Io(err) => err.code(),
+            RebarError::Hash(err) => err.code(),
+            RebarError::Object(err) => err.code(),
+            RebarError::Input(err) => err.code(),
+        }
+    }
+
+    /// The path this error occurred against, if any.
+    fn path(&self) -> Option<&str> {
+        match self {
+            RebarError::Io(err) => err.path(),
+            _ => None,
+        }
+    }
+
+    /// Render this error as a `{ "code", "message", "path"?, "cause"? }` JSON
+    /// object - `cause` is the error the sub-error wraps (the next link in
+    /// the `source()` chain after the sub-error, which `message` already
+    /// renders), if any. Hand-rolled rather than pulling in a JSON crate:
+    /// every value here is a plain string, so escaping is all that's needed.
+    /// The `rebar` binary prints errors in their [`Display`] form only; no
+    /// command-line flag selects this rendering.
+    pub fn to_json(&self) -> String {
+        let mut json = format!(
+            "{{\"code\":\"{}\",\"message\":\"{}\"",
+            self.code(),
+            json_escape(&self.to_string())
+        );
+        if let Some(path) = self.path() {
+            json.push_str(&format!(",\"path\":\"{}\"", json_escape(path)));
+        }
+        if let Some(cause) = std::error::Error::source(self).and_then(|err| err.source()) {
+            json.push_str(&format!(",\"cause\":\"{}\"", json_escape(&cause.to_string())));
+        }
+        json.push('}');
+        json
+    }
+}
+
+/// Escape a string for embedding as a JSON string value (quotes, backslashes,
+/// and control characters).
+fn json_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// The operation being attempted when a [`IoError::Path`] failure occurred.
+/// Mirrors kg-diag's `IoErrorDetail::IoPath { op_type, .. }`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpType {
+    Open,
+    Read,
+    Write,
+    Create,
+    Remove,
+    Metadata,
+}
+
+impl fmt::Display for OpType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verb = match self {
+            OpType::Open => "open",
+            OpType::Read => "read",
+            OpType::Write => "write to",
+            OpType::Create => "create",
+            OpType::Remove => "remove",
+            OpType::Metadata => "read metadata for",
+        };
+        write!(f, "{verb}")
+    }
+}
+
+/// The kind of filesystem entry an [`IoError::Path`] failure was attempted
+/// against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileType {
+    File,
+    Dir,
+    Symlink,
+}
+
+impl fmt::Display for FileType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let noun = match self {
+            FileType::File => "file",
+            FileType::Dir => "directory",
+            FileType::Symlink => "symlink",
+        };
+        write!(f, "{noun}")
+    }
+}
+
 #[derive(Debug)]
 pub enum IoError {
     Permission {
@@ -48,17 +160,91 @@ pub enum IoError {
     },
     EmptyPath,
     PathNotExists {
-        path: String,
+        path: OsString,
     },
     NotAFile {
-        path: String,
+        path: OsString,
     },
     NoRepository {
         path: String,
     },
+    /// A self-describing IO failure: which operation was attempted, against
+    /// what kind of filesystem entry, at what path. Prefer this over
+    /// [`IoError::Other`] at any call site that knows all three - see
+    /// [`IoError::create_file`] and friends.
+    Path {
+        op: OpType,
+        file_type: FileType,
+        path: String,
+        source: std::io::Error,
+    },
     Other(std::io::Error),
 }
 
+impl IoError {
+    /// A `std::fs::File::create` (or equivalent) failure.
+    pub fn create_file(path: impl Into<String>, source: std::io::Error) -> Self {
+        IoError::Path {
+            op: OpType::Create,
+            file_type: FileType::File,
+            path: path.into(),
+            source,
+        }
+    }
+
+    /// A `std::fs::File::open` (or equivalent) failure.
+    pub fn open_file(path: impl Into<String>, source: std::io::Error) -> Self {
+        IoError::Path {
+            op: OpType::Open,
+            file_type: FileType::File,
+            path: path.into(),
+            source,
+        }
+    }
+
+    /// A `std::fs::read`/`Read::read_to_end` (or equivalent) failure against
+    /// a regular file.
+    pub fn read_file(path: impl Into<String>, source: std::io::Error) -> Self {
+        IoError::Path {
+            op: OpType::Read,
+            file_type: FileType::File,
+            path: path.into(),
+            source,
+        }
+    }
+
+    /// A `Write::write_all` (or equivalent) failure against a regular file.
+    pub fn write_file(path: impl Into<String>, source: std::io::Error) -> Self {
+        IoError::Path {
+            op: OpType::Write,
+            file_type: FileType::File,
+            path: path.into(),
+            source,
+        }
+    }
+
+    /// A `std::fs::read_link` failure.
+    pub fn read_symlink(path: impl Into<String>, source: std::io::Error) -> Self {
+        IoError::Path {
+            op: OpType::Read,
+            file_type: FileType::Symlink,
+            path: path.into(),
+            source,
+        }
+    }
+
+    /// A `std::fs::symlink_metadata`/`std::fs::metadata` (or equivalent)
+    /// failure.
+    pub fn metadata(path: impl Into<String>, source: std::io::Error) -> Self {
+        IoError::Path {
+            op: OpType::Metadata,
+            file_type: FileType::File,
+            path: path.into(),
+            source,
+        }
+    }
+}
+
 impl fmt::Display for IoError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -68,11 +254,23 @@ impl fmt::Display for IoError {
             }
             IoError::NotFound { path } => write!(f, "File or directory not found: {path}"),
             IoError::EmptyPath => write!(f, "Path cannot be empty"),
-            IoError::PathNotExists { path } => write!(f, "Path does not exist: {path}"),
-            IoError::NotAFile { path } => write!(f, "Path is not a file: {path}"),
+            IoError::PathNotExists { path } => {
+                write!(f, "Path does not exist: {}", path.to_string_lossy())
+            }
+            IoError::NotAFile { path } => {
+                write!(f, "Path is not a file: {}", path.to_string_lossy())
+            }
             IoError::NoRepository { path } => {
                 write!(f, "Path '{path}' is not inside a Rebar repository")
             }
+            IoError::Path {
+                op,
+                file_type,
+                path,
+                source,
+            } => {
+                write!(f, "failed to {op} {file_type} '{path}': {source}")
+            }
             IoError::Other(err) => write!(f, "IO error: {err}"),
         }
     }
@@ -82,17 +280,54 @@ impl std::error::Error for IoError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
             IoError::Permission { source, .. } => Some(source),
+            IoError::Path { source, .. } => Some(source),
             IoError::Other(err) => Some(err),
             _ => None,
         }
     }
 }
 
+impl IoError {
+    /// A stable, machine-readable identifier for this error's variant, for
+    /// scripts/CI to match on instead of parsing [`Display`] prose.
+    pub fn code(&self) -> &'static str {
+        match self {
+            IoError::Permission { .. } => "rebar.io.permission_denied",
+            IoError::AlreadyExists { .. } => "rebar.io.already_exists",
+            IoError::NotFound { .. } => "rebar.io.not_found",
+            IoError::EmptyPath => "rebar.io.empty_path",
+            IoError::PathNotExists { .. } => "rebar.io.path_not_exists",
+            IoError::NotAFile { .. } => "rebar.io.not_a_file",
+            IoError::NoRepository { .. } => "rebar.io.no_repository",
+            IoError::Path { .. } => "rebar.io.path_error",
+            IoError::Other(_) => "rebar.io.other",
+        }
+    }
+
+    /// The path this error occurred against, if any.
+    pub fn path(&self) -> Option<&str> {
+        match self {
+            IoError::Permission { path, .. } => Some(path),
+            IoError::AlreadyExists { path } => Some(path),
+            IoError::NotFound { path } => Some(path),
+            IoError::NoRepository { path } => Some(path),
+            IoError::Path { path, .. } => Some(path),
+            IoError::PathNotExists { path } => path.to_str(),
+            IoError::NotAFile { path } => path.to_str(),
+            IoError::EmptyPath | IoError::Other(_) => None,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum InputError {
     ArgumentConflict { message: String },
     MissingArgument { argument: String },
     InvalidArgument { argument: String, reason: String },
+    /// A write was refused because its target already exists and the
+    /// caller didn't opt into overwriting it (see `commands::hash_object`'s
+    /// `force` parameter).
+    RequiresForce { path: String },
 }
 
 impl fmt::Display for InputError {
@@ -105,12 +340,27 @@ impl fmt::Display for InputError {
             InputError::InvalidArgument { argument, reason } => {
                 write!(f, "Invalid argument '{argument}': {reason}")
             }
+            InputError::RequiresForce { path } => {
+                write!(f, "'{path}' already exists; use --force to overwrite it")
+            }
         }
     }
 }
 
 impl std::error::Error for InputError {}
 
+impl InputError {
+    /// A stable, machine-readable identifier for this error's variant.
+    pub fn code(&self) -> &'static str {
+        match self {
+            InputError::ArgumentConflict { .. } => "rebar.input.argument_conflict",
+            InputError::MissingArgument { .. } => "rebar.input.missing_argument",
+            InputError::InvalidArgument { .. } => "rebar.input.invalid_argument",
+            InputError::RequiresForce { .. } => "rebar.input.requires_force",
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum HashError {
     InvalidLength { length: usize },
@@ -140,7 +390,18 @@ impl fmt::Display for HashError {
 
 impl std::error::Error for HashError {}
 
-#[derive(Debug)]
+impl HashError {
+    /// A stable, machine-readable identifier for this error's variant.
+    pub fn code(&self) -> &'static str {
+        match self {
+            HashError::InvalidLength { .. } => "rebar.hash.invalid_length",
+            HashError::InvalidCharacter { .. } => "rebar.hash.invalid_character",
+            HashError::Conversion(_) => "rebar.hash.conversion",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
 pub enum ObjectError {
     /// Invalid object type (not blob, tree, or commit)
     InvalidType { found: String },
@@ -159,6 +420,24 @@ pub enum ObjectError {
     MissingField { field: String, object_type: String },
     /// Failed to compress an object
     CompressionError { reason: String },
+    /// A stored chunk's recomputed hash didn't match the id it was filed under
+    BlockHashMismatch {
+        index: usize,
+        expected: crate::types::ObjectId,
+        actual: crate::types::ObjectId,
+    },
+    /// A pack archive entry was rejected by `unpack`'s hardening checks
+    /// (path traversal, size limits, entry count, or an invalid object name)
+    UnsafeArchiveEntry { reason: String },
+    /// Object content that must be UTF-8 text (a tree, commit or manifest)
+    /// contained an invalid byte sequence
+    Utf8InvalidEncoding { offset: usize, length: usize },
+    /// Object content that must be UTF-8 text ended partway through a
+    /// multi-byte sequence
+    Utf8UnexpectedEof { offset: usize },
+    /// A manifest referenced a chunk id that isn't present in the packed
+    /// chunk store (see [`crate::bundle::Bundle`])
+    MissingChunk { id: crate::types::ObjectId },
 }
 
 impl fmt::Display for ObjectError {
@@ -205,28 +484,115 @@ impl fmt::Display for ObjectError {
             ObjectError::CompressionError { reason } => {
                 write!(f, "Failed to compress object: {reason}")
             }
+            ObjectError::BlockHashMismatch {
+                index,
+                expected,
+                actual,
+            } => {
+                write!(
+                    f,
+                    "Block {index} hash mismatch: expected {expected}, got {actual}"
+                )
+            }
+            ObjectError::UnsafeArchiveEntry { reason } => {
+                write!(f, "Rejected unsafe pack archive entry: {reason}")
+            }
+            ObjectError::Utf8InvalidEncoding { offset, length } => {
+                write!(f, "invalid UTF-8 at byte {offset} ({length} bytes)")
+            }
+            ObjectError::Utf8UnexpectedEof { offset } => {
+                write!(f, "invalid UTF-8 at byte {offset} (unexpected end of input)")
+            }
+            ObjectError::MissingChunk { id } => {
+                write!(f, "Missing chunk {id} referenced by manifest")
+            }
         }
     }
 }
 
 impl std::error::Error for ObjectError {}
 
+impl ObjectError {
+    /// A stable, machine-readable identifier for this error's variant.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ObjectError::InvalidType { .. } => "rebar.object.invalid_type",
+            ObjectError::InvalidLength { .. } => "rebar.object.invalid_length",
+            ObjectError::MalformedHeader { .. } => "rebar.object.malformed_header",
+            ObjectError::CorruptedContent { .. } => "rebar.object.corrupted_content",
+            ObjectError::InvalidFormat { .. } => "rebar.object.invalid_format",
+            ObjectError::MissingField { .. } => "rebar.object.missing_field",
+            ObjectError::CompressionError { .. } => "rebar.object.compression_error",
+            ObjectError::BlockHashMismatch { .. } => "rebar.object.block_hash_mismatch",
+            ObjectError::UnsafeArchiveEntry { .. } => "rebar.object.unsafe_archive_entry",
+            ObjectError::Utf8InvalidEncoding { .. } => "rebar.object.utf8_invalid_encoding",
+            ObjectError::Utf8UnexpectedEof { .. } => "rebar.object.utf8_unexpected_eof",
+            ObjectError::MissingChunk { .. } => "rebar.object.missing_chunk",
+        }
+    }
+
+    /// Strictly decode `bytes` as UTF-8 text, reporting the byte offset (and,
+    /// for a genuinely invalid sequence, its length) of the first problem
+    /// instead of silently replacing it the way
+    /// [`String::from_utf8_lossy`] would. Used for object content that must
+    /// parse as text - a tree, commit or manifest - as opposed to a blob's
+    /// opaque bytes, which are never decoded.
+    pub fn decode_utf8(bytes: &[u8]) -> Result<&str, ObjectError> {
+        std::str::from_utf8(bytes).map_err(|err| {
+            let offset = err.valid_up_to();
+            match err.error_len() {
+                Some(length) => ObjectError::Utf8InvalidEncoding { offset, length },
+                None => ObjectError::Utf8UnexpectedEof { offset },
+            }
+        })
+    }
+}
+
 // Conversion traits for easy error propagation
 impl From<std::io::Error> for IoError {
     fn from(err: std::io::Error) -> Self {
-        match err.kind() {
-            std::io::ErrorKind::PermissionDenied => IoError::Permission {
-                path: "unknown".to_string(), // Default path when context is not available
-                source: err,
-            },
-            std::io::ErrorKind::AlreadyExists => IoError::AlreadyExists {
-                path: "unknown".to_string(),
-            },
-            std::io::ErrorKind::NotFound => IoError::NotFound {
-                path: "unknown".to_string(),
-            },
-            _ => IoError::Other(err),
-        }
+        // No call-site context is available through a blanket conversion, so
+        // the path is unknown here. Prefer `Result::with_path` at the call
+        // site, which carries the real path through this same mapping.
+        io_error_with_path(err, "unknown".to_string())
+    }
+}
+
+fn io_error_with_path(err: std::io::Error, path: String) -> IoError {
+    match err.kind() {
+        std::io::ErrorKind::PermissionDenied => IoError::Permission { path, source: err },
+        std::io::ErrorKind::AlreadyExists => IoError::AlreadyExists { path },
+        std::io::ErrorKind::NotFound => IoError::NotFound { path },
+        _ => IoError::Other(err),
+    }
+}
+
+/// Attaches the path an IO operation was performed against to any error it
+/// produces, so call sites don't lose that context to a blanket `From`
+/// conversion. Mirrors how Mercurial's Rust core annotates IO errors at the
+/// call site rather than at the point the error type is converted.
+pub trait IoResultExt<T> {
+    /// Map an IO error to an [`IoError`] carrying `path`.
+    fn with_path(self, path: impl Into<String>) -> Result<T, IoError>;
+
+    /// Like [`IoResultExt::with_path`], but only computes `path` on failure.
+    fn with_path_lazy<F, P>(self, path: F) -> Result<T, IoError>
+    where
+        F: FnOnce() -> P,
+        P: Into<String>;
+}
+
+impl<T> IoResultExt<T> for Result<T, std::io::Error> {
+    fn with_path(self, path: impl Into<String>) -> Result<T, IoError> {
+        self.map_err(|err| io_error_with_path(err, path.into()))
+    }
+
+    fn with_path_lazy<F, P>(self, path: F) -> Result<T, IoError>
+    where
+        F: FnOnce() -> P,
+        P: Into<String>,
+    {
+        self.map_err(|err| io_error_with_path(err, path().into()))
     }
 }
 
@@ -290,12 +656,12 @@ mod tests {
         assert_eq!(format!("{err}"), "Path cannot be empty");
 
         let err = IoError::PathNotExists {
-            path: "/test/path".to_string(),
+            path: OsString::from("/test/path"),
         };
         assert_eq!(format!("{err}"), "Path does not exist: /test/path");
 
         let err = IoError::NotAFile {
-            path: "/test/dir".to_string(),
+            path: OsString::from("/test/dir"),
         };
         assert_eq!(format!("{err}"), "Path is not a file: /test/dir");
 
@@ -324,6 +690,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_io_error_path_constructors() {
+        let source = || io::Error::new(io::ErrorKind::PermissionDenied, "denied");
+
+        let err = IoError::create_file("/repo/objects/ab12", source());
+        assert_eq!(
+            format!("{err}"),
+            "failed to create file '/repo/objects/ab12': denied"
+        );
+
+        let err = IoError::open_file("/repo/objects/ab12", source());
+        assert_eq!(
+            format!("{err}"),
+            "failed to open file '/repo/objects/ab12': denied"
+        );
+
+        let err = IoError::read_file("/some/file.bin", source());
+        assert_eq!(
+            format!("{err}"),
+            "failed to read file '/some/file.bin': denied"
+        );
+
+        let err = IoError::write_file("/some/file.bin", source());
+        assert_eq!(
+            format!("{err}"),
+            "failed to write to file '/some/file.bin': denied"
+        );
+
+        let err = IoError::read_symlink("/some/link", source());
+        assert_eq!(
+            format!("{err}"),
+            "failed to read symlink '/some/link': denied"
+        );
+
+        let err = IoError::metadata("/some/entry", source());
+        assert_eq!(
+            format!("{err}"),
+            "failed to read metadata for file '/some/entry': denied"
+        );
+    }
+
     #[test]
     fn test_input_error_display() {
         let err = InputError::ArgumentConflict {
@@ -344,6 +751,14 @@ mod tests {
             format!("{err}"),
             "Invalid argument 'count': must be positive"
         );
+
+        let err = InputError::RequiresForce {
+            path: "/repo/.rebar/objects/abc123".to_string(),
+        };
+        assert_eq!(
+            format!("{err}"),
+            "'/repo/.rebar/objects/abc123' already exists; use --force to overwrite it"
+        );
     }
 
     #[test]
@@ -388,6 +803,61 @@ mod tests {
             reason: "zstd failed".to_string(),
         };
         assert_eq!(format!("{err}"), "Failed to compress object: zstd failed");
+
+        let err = ObjectError::UnsafeArchiveEntry {
+            reason: "path traversal".to_string(),
+        };
+        assert_eq!(
+            format!("{err}"),
+            "Rejected unsafe pack archive entry: path traversal"
+        );
+
+        let err = ObjectError::Utf8InvalidEncoding {
+            offset: 1423,
+            length: 2,
+        };
+        assert_eq!(format!("{err}"), "invalid UTF-8 at byte 1423 (2 bytes)");
+
+        let err = ObjectError::Utf8UnexpectedEof { offset: 1423 };
+        assert_eq!(
+            format!("{err}"),
+            "invalid UTF-8 at byte 1423 (unexpected end of input)"
+        );
+
+        let id = crate::types::ObjectId::new(crate::types::HashKind::Sha256, vec![0xab; 32]);
+        let err = ObjectError::MissingChunk { id: id.clone() };
+        assert_eq!(
+            format!("{err}"),
+            format!("Missing chunk {id} referenced by manifest")
+        );
+    }
+
+    #[test]
+    fn test_decode_utf8_valid() {
+        assert_eq!(ObjectError::decode_utf8(b"hello"), Ok("hello"));
+    }
+
+    #[test]
+    fn test_decode_utf8_invalid_sequence() {
+        // 0xFF is never valid in UTF-8.
+        let bytes = b"hello \xffworld";
+        match ObjectError::decode_utf8(bytes) {
+            Err(ObjectError::Utf8InvalidEncoding { offset, length }) => {
+                assert_eq!(offset, 6);
+                assert_eq!(length, 1);
+            }
+            other => panic!("Expected Utf8InvalidEncoding, got: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_decode_utf8_truncated_sequence() {
+        // A lone leading byte of a 2-byte sequence, truncated at the end of input.
+        let bytes = b"hello \xc2";
+        match ObjectError::decode_utf8(bytes) {
+            Err(ObjectError::Utf8UnexpectedEof { offset }) => assert_eq!(offset, 6),
+            other => panic!("Expected Utf8UnexpectedEof, got: {other:?}"),
+        }
     }
 
     #[test]
@@ -502,4 +972,101 @@ mod tests {
         let rebar_err = RebarError::Hash(hash_err);
         assert!(rebar_err.source().is_some());
     }
+
+    #[test]
+    fn test_with_path_attaches_real_path() {
+        let result: Result<(), io::Error> =
+            Err(io::Error::new(io::ErrorKind::NotFound, "not found"));
+        match result.with_path("/tmp/missing") {
+            Err(IoError::NotFound { path }) => assert_eq!(path, "/tmp/missing"),
+            _ => panic!("Expected NotFound error"),
+        }
+    }
+
+    #[test]
+    fn test_with_path_ok_passes_through() {
+        let result: Result<u8, io::Error> = Ok(42);
+        assert_eq!(result.with_path("/tmp/file").unwrap(), 42);
+    }
+
+    #[test]
+    fn test_with_path_lazy_only_computes_on_error() {
+        let mut called = false;
+        let result: Result<u8, io::Error> = Ok(42);
+        let value = result
+            .with_path_lazy(|| {
+                called = true;
+                "/tmp/file".to_string()
+            })
+            .unwrap();
+        assert_eq!(value, 42);
+        assert!(!called);
+
+        let result: Result<u8, io::Error> =
+            Err(io::Error::new(io::ErrorKind::AlreadyExists, "exists"));
+        match result.with_path_lazy(|| "/tmp/other".to_string()) {
+            Err(IoError::AlreadyExists { path }) => assert_eq!(path, "/tmp/other"),
+            _ => panic!("Expected AlreadyExists error"),
+        }
+    }
+
+    #[test]
+    fn test_error_codes() {
+        let err = RebarError::Io(IoError::AlreadyExists {
+            path: "/tmp/object".to_string(),
+        });
+        assert_eq!(err.code(), "rebar.io.already_exists");
+
+        let err = RebarError::Object(ObjectError::CorruptedContent {
+            reason: "bad checksum".to_string(),
+        });
+        assert_eq!(err.code(), "rebar.object.corrupted_content");
+
+        let err = RebarError::Hash(HashError::InvalidLength { length: 32 });
+        assert_eq!(err.code(), "rebar.hash.invalid_length");
+
+        let err = RebarError::Input(InputError::MissingArgument {
+            argument: "path".to_string(),
+        });
+        assert_eq!(err.code(), "rebar.input.missing_argument");
+    }
+
+    #[test]
+    fn test_to_json_includes_path_for_io_errors() {
+        let err = RebarError::Io(IoError::NotFound {
+            path: "/tmp/missing".to_string(),
+        });
+        let json = err.to_json();
+        assert_eq!(
+            json,
+            "{\"code\":\"rebar.io.not_found\",\"message\":\"IO error: File or directory not found: /tmp/missing\",\"path\":\"/tmp/missing\"}"
+        );
+    }
+
+    #[test]
+    fn test_to_json_omits_path_for_non_io_errors() {
+        let err = RebarError::Hash(HashError::InvalidLength { length: 32 });
+        let json = err.to_json();
+        assert!(!json.contains("\"path\""));
+        assert!(json.contains("\"code\":\"rebar.hash.invalid_length\""));
+    }
+
+    #[test]
+    fn test_to_json_includes_cause_for_permission_errors() {
+        let source = io::Error::new(io::ErrorKind::PermissionDenied, "denied");
+        let err = RebarError::Io(IoError::Permission {
+            path: "/tmp/secret".to_string(),
+            source,
+        });
+        let json = err.to_json();
+        assert!(json.contains("\"cause\":\"denied\""));
+    }
+
+    #[test]
+    fn test_json_escape() {
+        assert_eq!(json_escape("plain"), "plain");
+        assert_eq!(json_escape("a \"quote\""), "a \\\"quote\\\"");
+        assert_eq!(json_escape("back\\slash"), "back\\\\slash");
+        assert_eq!(json_escape("line\nbreak"), "line\\nbreak");
+    }
 }
@@ -19,3 +19,19 @@ pub const FILE_SIZE_LIMIT: usize = const_parse_unsigned!(env!("FILE_SIZE_LIMIT")
 
 // Not dynamic as the hashing algorithm is fixed to SHA256
 pub const HASH_SIZE: u8 = 64;
+
+/// Build-time default zstd window log; `0` means "let zstd pick a
+/// level-derived default". Overridable per-repository at runtime - see
+/// `crate::config::Config::compression_window_log`.
+pub const COMPRESSION_WINDOW_LOG: u32 = const_parse_unsigned!(env!("COMPRESSION_WINDOW_LOG"), u32);
+
+const fn const_parse_bool(s: &str) -> bool {
+    let bytes = s.as_bytes();
+    bytes.len() == 4 && bytes[0] == b't' && bytes[1] == b'r' && bytes[2] == b'u' && bytes[3] == b'e'
+}
+
+/// Build-time default for zstd long-distance matching. Overridable
+/// per-repository at runtime - see
+/// `crate::config::Config::enable_long_distance_matching`.
+pub const ENABLE_LONG_DISTANCE_MATCHING: bool =
+    const_parse_bool(env!("ENABLE_LONG_DISTANCE_MATCHING"));
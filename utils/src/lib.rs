@@ -0,0 +1,14 @@
+//! Shared utilities for the Rebar VCS
+
+pub mod bundle;
+pub mod chunker;
+pub mod config;
+pub mod errors;
+pub mod globals;
+pub mod path;
+pub mod types;
+
+mod utils;
+
+pub use path::BytesContainer;
+pub use utils::{find_repository, validate_hex, validate_path};
@@ -0,0 +1,103 @@
+//! Byte-oriented path abstraction.
+//!
+//! Every path-taking function in this crate used to require `&str`, which
+//! silently breaks on the real-world Unix paths that aren't valid UTF-8.
+//! [`BytesContainer`] (named after the pre-1.0 `std::path::BytesContainer`,
+//! which served the same purpose) lets callers pass a `&str`/`String`,
+//! `&OsStr`/`OsString`, `&Path`/`PathBuf`, or raw `&[u8]`/`Vec<u8>` and have
+//! it handled as arbitrary bytes rather than assumed-UTF-8 text.
+
+use std::ffi::{OsStr, OsString};
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+
+pub trait BytesContainer {
+    fn container_as_bytes(&self) -> &[u8];
+
+    fn as_path(&self) -> &Path {
+        Path::new(OsStr::from_bytes(self.container_as_bytes()))
+    }
+}
+
+impl BytesContainer for str {
+    fn container_as_bytes(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl BytesContainer for String {
+    fn container_as_bytes(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl BytesContainer for OsStr {
+    fn container_as_bytes(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl BytesContainer for OsString {
+    fn container_as_bytes(&self) -> &[u8] {
+        self.as_os_str().as_bytes()
+    }
+}
+
+impl BytesContainer for Path {
+    fn container_as_bytes(&self) -> &[u8] {
+        self.as_os_str().as_bytes()
+    }
+}
+
+impl BytesContainer for PathBuf {
+    fn container_as_bytes(&self) -> &[u8] {
+        self.as_path().container_as_bytes()
+    }
+}
+
+impl BytesContainer for [u8] {
+    fn container_as_bytes(&self) -> &[u8] {
+        self
+    }
+}
+
+impl BytesContainer for Vec<u8> {
+    fn container_as_bytes(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_str_as_path() {
+        assert_eq!("foo/bar".as_path(), Path::new("foo/bar"));
+    }
+
+    #[test]
+    fn test_os_str_as_path() {
+        let os_str = OsStr::new("foo/bar");
+        assert_eq!(os_str.as_path(), Path::new("foo/bar"));
+    }
+
+    #[test]
+    fn test_bytes_as_path() {
+        let bytes: &[u8] = b"foo/bar";
+        assert_eq!(bytes.as_path(), Path::new("foo/bar"));
+    }
+
+    #[test]
+    fn test_non_utf8_bytes_as_path() {
+        let bytes: &[u8] = b"foo/\xff\xfe/bar";
+        let path = bytes.as_path();
+        assert_eq!(path.as_os_str().as_bytes(), bytes);
+    }
+
+    #[test]
+    fn test_path_buf_as_path() {
+        let path_buf = PathBuf::from("foo/bar");
+        assert_eq!(path_buf.as_path(), Path::new("foo/bar"));
+    }
+}
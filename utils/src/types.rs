@@ -1,15 +1,161 @@
 //! Common types for Rebar
 
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::str::FromStr;
 
-use crate::errors::{ObjectError, RebarError};
+use crate::errors::{HashError, ObjectError, RebarError};
+
+/// Digest algorithm an [`ObjectId`] was produced with.
+///
+/// `Sha256` is the only kind objects are currently hashed with; the others are
+/// reserved so the object model can grow alternative algorithms without
+/// changing every callsite that handles an id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HashKind {
+    Sha256,
+    Sha1,
+    Blake3,
+}
+
+impl HashKind {
+    /// Length of the raw digest, in bytes.
+    pub const fn digest_len(self) -> usize {
+        match self {
+            HashKind::Sha256 => 32,
+            HashKind::Sha1 => 20,
+            HashKind::Blake3 => 32,
+        }
+    }
+
+    /// Length of the digest when hex-encoded.
+    pub const fn hex_len(self) -> usize {
+        self.digest_len() * 2
+    }
+}
+
+/// An owned, algorithm-tagged object identifier.
+///
+/// Wraps the raw digest bytes of an object together with the [`HashKind`] that
+/// produced them, so the digest size is a property of the algorithm rather
+/// than a crate-wide constant, and so truncated or alternative hashers can be
+/// introduced without changing the type callers pass around.
+#[derive(Debug, Clone, Eq)]
+pub struct ObjectId {
+    kind: HashKind,
+    bytes: Vec<u8>,
+}
+
+impl ObjectId {
+    /// Build an `ObjectId` from already-computed digest bytes.
+    pub fn new(kind: HashKind, bytes: Vec<u8>) -> Self {
+        debug_assert_eq!(bytes.len(), kind.digest_len());
+        Self { kind, bytes }
+    }
+
+    pub fn kind(&self) -> HashKind {
+        self.kind
+    }
+
+    /// The raw digest bytes, without the `kind` tag.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Parse an `ObjectId` from its hex representation, assuming `kind`.
+    pub fn from_hex(hex: &str, kind: HashKind) -> Result<Self, HashError> {
+        if hex.len() != kind.hex_len() {
+            return Err(HashError::InvalidLength { length: hex.len() });
+        }
+
+        let chars: Vec<char> = hex.chars().collect();
+        let mut bytes = Vec::with_capacity(kind.digest_len());
+        for (i, pair) in chars.chunks(2).enumerate() {
+            // `hex.len()` counts bytes, so a multi-byte character can leave
+            // an odd number of chars and a lone final one, which is never a
+            // hex digit.
+            let [hi, lo] = pair else {
+                return Err(HashError::InvalidCharacter {
+                    position: i * 2,
+                    character: pair[0],
+                });
+            };
+            if !hi.is_ascii_hexdigit() {
+                return Err(HashError::InvalidCharacter {
+                    position: i * 2,
+                    character: *hi,
+                });
+            }
+            if !lo.is_ascii_hexdigit() {
+                return Err(HashError::InvalidCharacter {
+                    position: i * 2 + 1,
+                    character: *lo,
+                });
+            }
+
+            let high = hi.to_digit(16).unwrap() as u8;
+            let low = lo.to_digit(16).unwrap() as u8;
+            bytes.push((high << 4) | low);
+        }
+
+        Ok(Self { kind, bytes })
+    }
+
+    /// Render the digest as a lowercase hex string.
+    pub fn to_hex(&self) -> String {
+        hex::encode(&self.bytes)
+    }
+}
+
+impl PartialEq for ObjectId {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind && self.bytes == other.bytes
+    }
+}
+
+// Hash over the raw digest only, so a truncated or alternative hasher can
+// still be looked up by its bytes alone.
+impl Hash for ObjectId {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bytes.hash(state);
+    }
+}
+
+impl PartialOrd for ObjectId {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ObjectId {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.bytes.cmp(&other.bytes)
+    }
+}
+
+impl fmt::Display for ObjectId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_hex())
+    }
+}
+
+impl FromStr for ObjectId {
+    type Err = HashError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ObjectId::from_hex(s, HashKind::Sha256)
+    }
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ObjectType {
     Blob,
-    // TODO: Implement other object types
-    // Tree,
-    // Commit,
+    Tree,
+    Commit,
+    /// An ordered list of chunk ids, used in place of a blob when content is
+    /// too large to store as a single object. See [`Manifest`].
+    Manifest,
 }
 
 impl FromStr for ObjectType {
@@ -18,8 +164,9 @@ impl FromStr for ObjectType {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "blob" => Ok(ObjectType::Blob),
-            // "tree" => Ok(ObjectType::Tree),
-            // "commit" => Ok(ObjectType::Commit),
+            "tree" => Ok(ObjectType::Tree),
+            "commit" => Ok(ObjectType::Commit),
+            "manifest" => Ok(ObjectType::Manifest),
             _ => Err(RebarError::Object(ObjectError::InvalidType {
                 found: s.to_string(),
             })),
@@ -27,6 +174,368 @@ impl FromStr for ObjectType {
     }
 }
 
+/// A single entry in a [`Tree`]: a name plus the mode and id of the object it
+/// points to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeEntry {
+    pub mode: String,
+    pub name: String,
+    pub id: ObjectId,
+}
+
+/// A directory listing: a sorted-by-name list of entries, each pointing at a
+/// blob or another tree.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Tree {
+    pub entries: Vec<TreeEntry>,
+}
+
+impl Tree {
+    /// Build a tree, sorting its entries by name as stored trees always are.
+    pub fn new(mut entries: Vec<TreeEntry>) -> Self {
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        Self { entries }
+    }
+
+    /// Serialize to the on-disk format: one `<mode> <name> <id>` line per
+    /// entry, with `name` percent-escaped so a space or newline in a real
+    /// filename can't be mistaken for a field separator.
+    pub fn serialize(&self) -> String {
+        self.entries
+            .iter()
+            .map(|e| format!("{} {} {}\n", e.mode, escape_name(&e.name), e.id))
+            .collect()
+    }
+
+    /// Parse the on-disk format produced by [`Tree::serialize`].
+    pub fn parse(data: &str) -> Result<Self, ObjectError> {
+        let mut entries = Vec::new();
+
+        for line in data.lines() {
+            let mut parts = line.splitn(3, ' ');
+            let mode = parts.next().filter(|s| !s.is_empty()).ok_or_else(|| {
+                ObjectError::MissingField {
+                    field: "mode".to_string(),
+                    object_type: "tree".to_string(),
+                }
+            })?;
+            let name = parts.next().ok_or_else(|| ObjectError::MissingField {
+                field: "name".to_string(),
+                object_type: "tree".to_string(),
+            })?;
+            let id_str = parts.next().ok_or_else(|| ObjectError::MissingField {
+                field: "id".to_string(),
+                object_type: "tree".to_string(),
+            })?;
+            let id = ObjectId::from_hex(id_str, HashKind::Sha256).map_err(|e| {
+                ObjectError::InvalidFormat {
+                    object_type: "tree".to_string(),
+                    reason: format!("entry '{name}': {e}"),
+                }
+            })?;
+            let name = unescape_name(name).map_err(|reason| ObjectError::InvalidFormat {
+                object_type: "tree".to_string(),
+                reason: format!("entry '{name}': {reason}"),
+            })?;
+
+            entries.push(TreeEntry {
+                mode: mode.to_string(),
+                name,
+                id,
+            });
+        }
+
+        Ok(Self { entries })
+    }
+}
+
+/// Percent-escape the bytes that would otherwise be mistaken for a `Tree`
+/// entry-line delimiter (`' '`, `'\n'`) or for the start of an escape
+/// sequence (`'%'`) - ordinary filenames round-trip unchanged.
+fn escape_name(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    for c in name.chars() {
+        match c {
+            ' ' => out.push_str("%20"),
+            '\n' => out.push_str("%0a"),
+            '%' => out.push_str("%25"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Reverse [`escape_name`].
+fn unescape_name(escaped: &str) -> Result<String, String> {
+    let bytes = escaped.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = bytes
+                .get(i + 1..i + 3)
+                .and_then(|h| std::str::from_utf8(h).ok())
+                .ok_or_else(|| format!("truncated escape sequence in name '{escaped}'"))?;
+            let value = u8::from_str_radix(hex, 16)
+                .map_err(|_| format!("invalid escape sequence '%{hex}' in name '{escaped}'"))?;
+            out.push(value);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(out).map_err(|_| format!("name '{escaped}' is not valid UTF-8 after unescaping"))
+}
+
+/// The author or committer of a [`Commit`]: a name, email, and the time the
+/// action took place.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signature {
+    pub name: String,
+    pub email: String,
+    pub timestamp: i64,
+}
+
+impl Signature {
+    fn parse(s: &str, object_type: &str) -> Result<Self, ObjectError> {
+        let email_start = s.find('<').ok_or_else(|| ObjectError::InvalidFormat {
+            object_type: object_type.to_string(),
+            reason: format!("signature '{s}' is missing '<email>'"),
+        })?;
+        let email_end = s.find('>').ok_or_else(|| ObjectError::InvalidFormat {
+            object_type: object_type.to_string(),
+            reason: format!("signature '{s}' is missing '<email>'"),
+        })?;
+
+        let name = s[..email_start].trim().to_string();
+        let email = s[email_start + 1..email_end].to_string();
+        let timestamp_str = s[email_end + 1..].trim();
+        let timestamp = timestamp_str
+            .parse::<i64>()
+            .map_err(|_| ObjectError::InvalidFormat {
+                object_type: object_type.to_string(),
+                reason: format!("invalid timestamp '{timestamp_str}'"),
+            })?;
+
+        Ok(Self {
+            name,
+            email,
+            timestamp,
+        })
+    }
+
+    fn serialize(&self) -> String {
+        format!("{} <{}> {}", self.name, self.email, self.timestamp)
+    }
+}
+
+/// A point in the project's history: the tree it records, the commits it
+/// builds on, who made it and when, and why.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Commit {
+    pub tree: ObjectId,
+    pub parents: Vec<ObjectId>,
+    pub author: Signature,
+    pub committer: Signature,
+    pub message: String,
+}
+
+impl Commit {
+    /// Serialize to the on-disk format: a `tree`/`parent`/`author`/`committer`
+    /// header followed by a blank line and the free-form message.
+    pub fn serialize(&self) -> String {
+        let mut out = format!("tree {}\n", self.tree);
+        for parent in &self.parents {
+            out += &format!("parent {parent}\n");
+        }
+        out += &format!("author {}\n", self.author.serialize());
+        out += &format!("committer {}\n", self.committer.serialize());
+        out += "\n";
+        out += &self.message;
+        out
+    }
+
+    /// Parse the on-disk format produced by [`Commit::serialize`].
+    pub fn parse(data: &str) -> Result<Self, ObjectError> {
+        let mut tree = None;
+        let mut parents = Vec::new();
+        let mut author = None;
+        let mut committer = None;
+
+        let mut lines = data.lines();
+        for line in lines.by_ref() {
+            if line.is_empty() {
+                break;
+            }
+
+            let (key, value) = line.split_once(' ').ok_or_else(|| ObjectError::InvalidFormat {
+                object_type: "commit".to_string(),
+                reason: format!("malformed header line '{line}'"),
+            })?;
+
+            match key {
+                "tree" => {
+                    tree = Some(ObjectId::from_hex(value, HashKind::Sha256).map_err(|e| {
+                        ObjectError::InvalidFormat {
+                            object_type: "commit".to_string(),
+                            reason: format!("invalid tree id: {e}"),
+                        }
+                    })?);
+                }
+                "parent" => {
+                    parents.push(ObjectId::from_hex(value, HashKind::Sha256).map_err(|e| {
+                        ObjectError::InvalidFormat {
+                            object_type: "commit".to_string(),
+                            reason: format!("invalid parent id: {e}"),
+                        }
+                    })?);
+                }
+                "author" => author = Some(Signature::parse(value, "commit")?),
+                "committer" => committer = Some(Signature::parse(value, "commit")?),
+                _ => {
+                    return Err(ObjectError::InvalidFormat {
+                        object_type: "commit".to_string(),
+                        reason: format!("unknown header field '{key}'"),
+                    });
+                }
+            }
+        }
+
+        let tree = tree.ok_or_else(|| ObjectError::MissingField {
+            field: "tree".to_string(),
+            object_type: "commit".to_string(),
+        })?;
+        let author = author.ok_or_else(|| ObjectError::MissingField {
+            field: "author".to_string(),
+            object_type: "commit".to_string(),
+        })?;
+        let committer = committer.ok_or_else(|| ObjectError::MissingField {
+            field: "committer".to_string(),
+            object_type: "commit".to_string(),
+        })?;
+
+        let message = lines.collect::<Vec<_>>().join("\n");
+
+        Ok(Self {
+            tree,
+            parents,
+            author,
+            committer,
+            message,
+        })
+    }
+}
+
+/// An ordered list of content-defined chunk ids that together reconstitute a
+/// blob too large to store as a single object.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Manifest {
+    pub chunks: Vec<ObjectId>,
+}
+
+impl Manifest {
+    /// Serialize to the on-disk format: one chunk id per line, in order.
+    pub fn serialize(&self) -> String {
+        self.chunks.iter().map(|id| format!("{id}\n")).collect()
+    }
+
+    /// Parse the on-disk format produced by [`Manifest::serialize`].
+    pub fn parse(data: &str) -> Result<Self, ObjectError> {
+        let mut chunks = Vec::new();
+
+        for (index, line) in data.lines().enumerate() {
+            let id = ObjectId::from_hex(line, HashKind::Sha256).map_err(|e| {
+                ObjectError::InvalidFormat {
+                    object_type: "manifest".to_string(),
+                    reason: format!("chunk {index}: {e}"),
+                }
+            })?;
+            chunks.push(id);
+        }
+
+        Ok(Self { chunks })
+    }
+}
+
+/// The filesystem entry type and permission bits a blob (or the manifest of
+/// a chunked blob) was created from, so a working tree can be reconstructed
+/// faithfully instead of treating every object as an opaque, type-less blob.
+/// Stored as a header field alongside the object type and length; unlike a
+/// block or char device, a regular file's or symlink's body is still the
+/// object content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileMode {
+    Regular { executable: bool },
+    Symlink,
+    Fifo,
+    BlockDevice { major: u32, minor: u32 },
+    CharDevice { major: u32, minor: u32 },
+}
+
+impl FileMode {
+    /// Render as the header-field string. Object types that carry no mode
+    /// (tree, commit) use the literal `"-"` instead, handled by the caller.
+    pub fn serialize(&self) -> String {
+        match self {
+            FileMode::Regular { executable: false } => "file".to_string(),
+            FileMode::Regular { executable: true } => "file+x".to_string(),
+            FileMode::Symlink => "symlink".to_string(),
+            FileMode::Fifo => "fifo".to_string(),
+            FileMode::BlockDevice { major, minor } => format!("blockdev:{major}:{minor}"),
+            FileMode::CharDevice { major, minor } => format!("chardev:{major}:{minor}"),
+        }
+    }
+
+    /// Parse the header-field string produced by [`FileMode::serialize`].
+    pub fn parse(s: &str) -> Result<Self, ObjectError> {
+        match s {
+            "file" => Ok(FileMode::Regular { executable: false }),
+            "file+x" => Ok(FileMode::Regular { executable: true }),
+            "symlink" => Ok(FileMode::Symlink),
+            "fifo" => Ok(FileMode::Fifo),
+            other => {
+                if let Some(rest) = other.strip_prefix("blockdev:") {
+                    let (major, minor) = parse_device_numbers(rest, "blockdev")?;
+                    Ok(FileMode::BlockDevice { major, minor })
+                } else if let Some(rest) = other.strip_prefix("chardev:") {
+                    let (major, minor) = parse_device_numbers(rest, "chardev")?;
+                    Ok(FileMode::CharDevice { major, minor })
+                } else {
+                    Err(ObjectError::MalformedHeader {
+                        reason: format!("unknown file mode '{other}'"),
+                    })
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for FileMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.serialize())
+    }
+}
+
+fn parse_device_numbers(s: &str, kind: &str) -> Result<(u32, u32), ObjectError> {
+    let (major_str, minor_str) = s
+        .split_once(':')
+        .ok_or_else(|| ObjectError::MalformedHeader {
+            reason: format!("malformed {kind} mode '{s}'"),
+        })?;
+    let major = major_str
+        .parse()
+        .map_err(|_| ObjectError::MalformedHeader {
+            reason: format!("invalid {kind} major '{major_str}'"),
+        })?;
+    let minor = minor_str
+        .parse()
+        .map_err(|_| ObjectError::MalformedHeader {
+            reason: format!("invalid {kind} minor '{minor_str}'"),
+        })?;
+    Ok((major, minor))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -97,4 +606,271 @@ mod tests {
         let obj2 = obj1.clone();
         assert_eq!(obj1, obj2);
     }
+
+    #[test]
+    fn test_object_id_from_hex_roundtrip() {
+        let hex = "a".repeat(64);
+        let id = ObjectId::from_hex(&hex, HashKind::Sha256).unwrap();
+        assert_eq!(id.kind(), HashKind::Sha256);
+        assert_eq!(id.to_hex(), hex);
+        assert_eq!(id.as_bytes().len(), 32);
+    }
+
+    #[test]
+    fn test_object_id_from_hex_invalid_length() {
+        let result = ObjectId::from_hex("abc123", HashKind::Sha256);
+        match result {
+            Err(HashError::InvalidLength { length }) => assert_eq!(length, 6),
+            _ => panic!("Expected InvalidLength error"),
+        }
+    }
+
+    #[test]
+    fn test_object_id_from_hex_invalid_character() {
+        let hex = "g".repeat(64);
+        let result = ObjectId::from_hex(&hex, HashKind::Sha256);
+        match result {
+            Err(HashError::InvalidCharacter { position, character }) => {
+                assert_eq!(position, 0);
+                assert_eq!(character, 'g');
+            }
+            _ => panic!("Expected InvalidCharacter error"),
+        }
+    }
+
+    #[test]
+    fn test_object_id_from_str_defaults_to_sha256() {
+        let hex = "b".repeat(64);
+        let id = ObjectId::from_str(&hex).unwrap();
+        assert_eq!(id.kind(), HashKind::Sha256);
+    }
+
+    #[test]
+    fn test_object_id_display() {
+        let hex = "c".repeat(64);
+        let id = ObjectId::from_hex(&hex, HashKind::Sha256).unwrap();
+        assert_eq!(format!("{id}"), hex);
+    }
+
+    #[test]
+    fn test_object_id_ord_and_hash_over_raw_bytes() {
+        use std::collections::HashSet;
+
+        let low = ObjectId::new(HashKind::Sha256, vec![0x00; 32]);
+        let high = ObjectId::new(HashKind::Sha1, vec![0xff; 20]);
+        assert_eq!(low.cmp(&high), Ordering::Less);
+
+        let mut set = HashSet::new();
+        set.insert(ObjectId::new(HashKind::Sha256, vec![1; 32]));
+        assert!(set.contains(&ObjectId::new(HashKind::Sha256, vec![1; 32])));
+    }
+
+    #[test]
+    fn test_hash_kind_lengths() {
+        assert_eq!(HashKind::Sha256.digest_len(), 32);
+        assert_eq!(HashKind::Sha256.hex_len(), 64);
+        assert_eq!(HashKind::Sha1.digest_len(), 20);
+        assert_eq!(HashKind::Blake3.digest_len(), 32);
+    }
+
+    #[test]
+    fn test_object_type_from_str_tree_and_commit() {
+        assert_eq!(ObjectType::from_str("tree").unwrap(), ObjectType::Tree);
+        assert_eq!(ObjectType::from_str("commit").unwrap(), ObjectType::Commit);
+    }
+
+    fn sample_id(byte: u8) -> ObjectId {
+        ObjectId::new(HashKind::Sha256, vec![byte; 32])
+    }
+
+    #[test]
+    fn test_tree_new_sorts_entries_by_name() {
+        let tree = Tree::new(vec![
+            TreeEntry {
+                mode: "100644".to_string(),
+                name: "z.txt".to_string(),
+                id: sample_id(1),
+            },
+            TreeEntry {
+                mode: "100644".to_string(),
+                name: "a.txt".to_string(),
+                id: sample_id(2),
+            },
+        ]);
+        assert_eq!(tree.entries[0].name, "a.txt");
+        assert_eq!(tree.entries[1].name, "z.txt");
+    }
+
+    #[test]
+    fn test_tree_serialize_parse_roundtrip() {
+        let tree = Tree::new(vec![TreeEntry {
+            mode: "100644".to_string(),
+            name: "file.txt".to_string(),
+            id: sample_id(3),
+        }]);
+
+        let serialized = tree.serialize();
+        let parsed = Tree::parse(&serialized).unwrap();
+        assert_eq!(parsed, tree);
+    }
+
+    #[test]
+    fn test_tree_serialize_parse_roundtrip_with_space_in_name() {
+        let tree = Tree::new(vec![TreeEntry {
+            mode: "100644".to_string(),
+            name: "my file.txt".to_string(),
+            id: sample_id(3),
+        }]);
+
+        let serialized = tree.serialize();
+        assert!(!serialized.contains("my file.txt"));
+        let parsed = Tree::parse(&serialized).unwrap();
+        assert_eq!(parsed, tree);
+    }
+
+    #[test]
+    fn test_tree_parse_missing_id() {
+        let result = Tree::parse("100644 file.txt");
+        match result {
+            Err(ObjectError::MissingField { field, object_type }) => {
+                assert_eq!(field, "id");
+                assert_eq!(object_type, "tree");
+            }
+            _ => panic!("Expected MissingField error"),
+        }
+    }
+
+    #[test]
+    fn test_tree_parse_invalid_id() {
+        let result = Tree::parse("100644 file.txt not-a-hash");
+        match result {
+            Err(ObjectError::InvalidFormat { object_type, .. }) => {
+                assert_eq!(object_type, "tree");
+            }
+            _ => panic!("Expected InvalidFormat error"),
+        }
+    }
+
+    fn sample_commit() -> Commit {
+        Commit {
+            tree: sample_id(4),
+            parents: vec![sample_id(5)],
+            author: Signature {
+                name: "Jane Doe".to_string(),
+                email: "jane@example.com".to_string(),
+                timestamp: 1_700_000_000,
+            },
+            committer: Signature {
+                name: "Jane Doe".to_string(),
+                email: "jane@example.com".to_string(),
+                timestamp: 1_700_000_001,
+            },
+            message: "Initial commit\n\nBody text.".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_commit_serialize_parse_roundtrip() {
+        let commit = sample_commit();
+        let serialized = commit.serialize();
+        let parsed = Commit::parse(&serialized).unwrap();
+        assert_eq!(parsed, commit);
+    }
+
+    #[test]
+    fn test_commit_parse_missing_tree() {
+        let data = "author Jane Doe <jane@example.com> 1700000000\ncommitter Jane Doe <jane@example.com> 1700000000\n\nmsg";
+        match Commit::parse(data) {
+            Err(ObjectError::MissingField { field, object_type }) => {
+                assert_eq!(field, "tree");
+                assert_eq!(object_type, "commit");
+            }
+            _ => panic!("Expected MissingField error"),
+        }
+    }
+
+    #[test]
+    fn test_commit_parse_no_parents() {
+        let commit = Commit {
+            parents: vec![],
+            ..sample_commit()
+        };
+        let parsed = Commit::parse(&commit.serialize()).unwrap();
+        assert!(parsed.parents.is_empty());
+    }
+
+    #[test]
+    fn test_signature_parse_invalid_timestamp() {
+        let result = Signature::parse("Jane Doe <jane@example.com> notanumber", "commit");
+        match result {
+            Err(ObjectError::InvalidFormat { object_type, .. }) => {
+                assert_eq!(object_type, "commit");
+            }
+            _ => panic!("Expected InvalidFormat error"),
+        }
+    }
+
+    #[test]
+    fn test_object_type_from_str_manifest() {
+        assert_eq!(
+            ObjectType::from_str("manifest").unwrap(),
+            ObjectType::Manifest
+        );
+    }
+
+    #[test]
+    fn test_manifest_serialize_parse_roundtrip() {
+        let manifest = Manifest {
+            chunks: vec![sample_id(1), sample_id(2), sample_id(3)],
+        };
+        let parsed = Manifest::parse(&manifest.serialize()).unwrap();
+        assert_eq!(parsed, manifest);
+    }
+
+    #[test]
+    fn test_manifest_parse_invalid_chunk_id() {
+        let result = Manifest::parse("not-a-hash");
+        match result {
+            Err(ObjectError::InvalidFormat { object_type, .. }) => {
+                assert_eq!(object_type, "manifest");
+            }
+            _ => panic!("Expected InvalidFormat error"),
+        }
+    }
+
+    #[test]
+    fn test_file_mode_serialize_parse_roundtrip() {
+        let modes = [
+            FileMode::Regular { executable: false },
+            FileMode::Regular { executable: true },
+            FileMode::Symlink,
+            FileMode::Fifo,
+            FileMode::BlockDevice { major: 8, minor: 1 },
+            FileMode::CharDevice { major: 5, minor: 1 },
+        ];
+
+        for mode in modes {
+            let parsed = FileMode::parse(&mode.serialize()).unwrap();
+            assert_eq!(parsed, mode);
+        }
+    }
+
+    #[test]
+    fn test_file_mode_display_matches_serialize() {
+        let mode = FileMode::BlockDevice { major: 8, minor: 1 };
+        assert_eq!(mode.to_string(), mode.serialize());
+    }
+
+    #[test]
+    fn test_file_mode_parse_invalid() {
+        match FileMode::parse("bogus") {
+            Err(ObjectError::MalformedHeader { .. }) => {}
+            _ => panic!("Expected MalformedHeader error"),
+        }
+
+        match FileMode::parse("blockdev:not-a-number:1") {
+            Err(ObjectError::MalformedHeader { .. }) => {}
+            _ => panic!("Expected MalformedHeader error"),
+        }
+    }
 }
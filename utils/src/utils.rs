@@ -1,8 +1,10 @@
 //! Core commands for the Rebar VCS
 
-use crate::errors::{HashError, IoError};
+use std::path::PathBuf;
 
+use crate::errors::{HashError, IoError};
 use crate::globals::HASH_SIZE;
+use crate::path::BytesContainer;
 
 /// Check if a hash contains an invalid character
 fn check_invalid_char(hash: &str) -> Option<usize> {
@@ -29,16 +31,18 @@ pub fn validate_hex(hex: &str) -> Result<(), HashError> {
 }
 
 /// Validate a file path exists and is a file
-pub fn validate_path(path: &str) -> Result<(), IoError> {
-    if path.is_empty() {
+pub fn validate_path<P: BytesContainer + ?Sized>(path: &P) -> Result<(), IoError> {
+    let path_ref = path.as_path();
+
+    if path.container_as_bytes().is_empty() {
         Err(IoError::EmptyPath)
-    } else if !std::path::Path::new(path).exists() {
+    } else if !path_ref.exists() {
         Err(IoError::PathNotExists {
-            path: path.to_string(),
+            path: path_ref.as_os_str().to_owned(),
         })
-    } else if !std::path::Path::new(path).is_file() {
+    } else if !path_ref.is_file() {
         Err(IoError::NotAFile {
-            path: path.to_string(),
+            path: path_ref.as_os_str().to_owned(),
         })
     } else {
         Ok(())
@@ -46,13 +50,13 @@ pub fn validate_path(path: &str) -> Result<(), IoError> {
 }
 
 /// Path to the closest .rebar directory
-pub fn find_repository(path: &str) -> Result<String, IoError> {
-    let mut current = std::path::Path::new(path);
+pub fn find_repository<P: BytesContainer + ?Sized>(path: &P) -> Result<PathBuf, IoError> {
+    let mut current = path.as_path();
 
     // Check current directory first, then traverse up the directory tree
     loop {
         if current.join(".rebar").exists() {
-            return Ok(current.join(".rebar").to_string_lossy().into_owned());
+            return Ok(current.join(".rebar"));
         }
 
         // Move to parent directory
@@ -64,7 +68,7 @@ pub fn find_repository(path: &str) -> Result<String, IoError> {
     }
 
     Err(IoError::NoRepository {
-        path: path.to_string(),
+        path: path.as_path().to_string_lossy().into_owned(),
     })
 }
 
@@ -175,7 +179,7 @@ mod tests {
         let nonexistent_path = "/path/that/does/not/exist/file.txt";
         match validate_path(nonexistent_path) {
             Err(IoError::PathNotExists { path }) => {
-                assert_eq!(path, nonexistent_path);
+                assert_eq!(path, std::ffi::OsString::from(nonexistent_path));
             }
             _ => panic!("Expected PathNotExists error"),
         }
@@ -187,10 +191,9 @@ mod tests {
         let dir_path = temp_dir.path().join("test_dir");
         fs::create_dir(&dir_path)?;
 
-        let path_str = dir_path.to_str().unwrap();
-        match validate_path(path_str) {
+        match validate_path(&dir_path) {
             Err(IoError::NotAFile { path }) => {
-                assert_eq!(path, path_str);
+                assert_eq!(path, dir_path.as_os_str());
             }
             _ => panic!("Expected NotAFile error"),
         }
@@ -204,14 +207,14 @@ mod tests {
         fs::create_dir(&rebar_dir)?;
 
         // Test from the directory containing .rebar
-        let result = find_repository(temp_dir.path().to_str().unwrap())?;
-        assert_eq!(result, rebar_dir.to_str().unwrap());
+        let result = find_repository(temp_dir.path())?;
+        assert_eq!(result, rebar_dir);
 
         // Test from a subdirectory
         let sub_dir = temp_dir.path().join("subdir");
         fs::create_dir(&sub_dir)?;
-        let result = find_repository(sub_dir.to_str().unwrap())?;
-        assert_eq!(result, rebar_dir.to_str().unwrap());
+        let result = find_repository(&sub_dir)?;
+        assert_eq!(result, rebar_dir);
 
         Ok(())
     }
@@ -232,6 +235,37 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_validate_path_non_utf8_file_name() -> Result<(), Box<dyn std::error::Error>> {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let temp_dir = TempDir::new()?;
+        let file_name = OsStr::from_bytes(b"not-\xffutf8");
+        let file_path = temp_dir.path().join(file_name);
+        fs::write(&file_path, "test content")?;
+
+        assert!(validate_path(&file_path).is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_repository_non_utf8_start_path() -> Result<(), Box<dyn std::error::Error>> {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let temp_dir = TempDir::new()?;
+        let rebar_dir = temp_dir.path().join(".rebar");
+        fs::create_dir(&rebar_dir)?;
+
+        let sub_dir = temp_dir.path().join(OsStr::from_bytes(b"sub-\xffdir"));
+        fs::create_dir(&sub_dir)?;
+
+        let result = find_repository(&sub_dir)?;
+        assert_eq!(result, rebar_dir);
+        Ok(())
+    }
+
     #[test]
     fn test_find_repository_nested() -> Result<(), Box<dyn std::error::Error>> {
         let temp_dir = TempDir::new()?;
@@ -242,8 +276,8 @@ mod tests {
         let nested_path = temp_dir.path().join("a").join("b").join("c");
         fs::create_dir_all(&nested_path)?;
 
-        let result = find_repository(nested_path.to_str().unwrap())?;
-        assert_eq!(result, rebar_dir.to_str().unwrap());
+        let result = find_repository(&nested_path)?;
+        assert_eq!(result, rebar_dir);
 
         Ok(())
     }